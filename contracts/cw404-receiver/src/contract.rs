@@ -0,0 +1,163 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+use cw404::Cw404Contract;
+use cw721::Cw721ReceiveMsg;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, FungibleEscrowResponse, InnerMsg, InstantiateMsg, NftEscrowResponse, QueryMsg,
+};
+use crate::state::{FUNGIBLE_ESCROW, NFT_ESCROW};
+use crate::{CONTRACT_NAME, CONTRACT_VERSION};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(receive) => receive_fungible(deps, info, receive),
+        ExecuteMsg::ReceiveNft(receive) => receive_nft(deps, info, receive),
+        ExecuteMsg::ReleaseFungible { token } => release_fungible(deps, info, token),
+        ExecuteMsg::ReleaseNft { token, token_id } => release_nft(deps, info, token, token_id),
+    }
+}
+
+/// `info.sender` is the cw404 contract, `receive.sender` the account that called `Send`.
+fn receive_fungible(
+    deps: DepsMut,
+    info: MessageInfo,
+    receive: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let res = Response::new()
+        .add_attribute("action", "receive")
+        .add_attribute("token", info.sender.as_str())
+        .add_attribute("sender", &receive.sender)
+        .add_attribute("amount", receive.amount);
+
+    match from_json(&receive.msg)? {
+        InnerMsg::Succeed {} => Ok(res.add_attribute("mode", "succeed")),
+        InnerMsg::Fail {} => Err(ContractError::Rejected {}),
+        InnerMsg::Escrow {} => {
+            let sender = deps.api.addr_validate(&receive.sender)?;
+            FUNGIBLE_ESCROW.update(
+                deps.storage,
+                (&info.sender, &sender),
+                |escrowed| -> StdResult<_> {
+                    Ok(escrowed.unwrap_or_default().checked_add(receive.amount)?)
+                },
+            )?;
+            Ok(res.add_attribute("mode", "escrow"))
+        }
+    }
+}
+
+/// `info.sender` is the cw404 contract, `receive.sender` the account that called `SendNft`.
+fn receive_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    receive: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let res = Response::new()
+        .add_attribute("action", "receive_nft")
+        .add_attribute("token", info.sender.as_str())
+        .add_attribute("sender", &receive.sender)
+        .add_attribute("token_id", &receive.token_id);
+
+    match from_json(&receive.msg)? {
+        InnerMsg::Succeed {} => Ok(res.add_attribute("mode", "succeed")),
+        InnerMsg::Fail {} => Err(ContractError::Rejected {}),
+        InnerMsg::Escrow {} => {
+            let sender = deps.api.addr_validate(&receive.sender)?;
+            NFT_ESCROW.save(deps.storage, (&info.sender, &receive.token_id), &sender)?;
+            // Releasing escrowed fungible units must not burn it. The release
+            // transfer unlocks it again.
+            let lock = Cw404Contract(info.sender).lock(&receive.token_id)?;
+            Ok(res.add_message(lock).add_attribute("mode", "escrow"))
+        }
+    }
+}
+
+fn release_fungible(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: String,
+) -> Result<Response, ContractError> {
+    let token = deps.api.addr_validate(&token)?;
+    let amount = FUNGIBLE_ESCROW
+        .may_load(deps.storage, (&token, &info.sender))?
+        .unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NothingEscrowed {
+            owner: info.sender.into_string(),
+        });
+    }
+    FUNGIBLE_ESCROW.remove(deps.storage, (&token, &info.sender));
+
+    let transfer = Cw404Contract(token).transfer(&info.sender, amount)?;
+    Ok(Response::new()
+        .add_message(transfer)
+        .add_attribute("action", "release_fungible")
+        .add_attribute("recipient", info.sender)
+        .add_attribute("amount", amount))
+}
+
+fn release_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let token = deps.api.addr_validate(&token)?;
+    let owner = NFT_ESCROW.may_load(deps.storage, (&token, &token_id))?;
+    if owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::NotEscrowed { token_id });
+    }
+    NFT_ESCROW.remove(deps.storage, (&token, &token_id));
+
+    let transfer = Cw404Contract(token).transfer_nft(&info.sender, &token_id)?;
+    Ok(Response::new()
+        .add_message(transfer)
+        .add_attribute("action", "release_nft")
+        .add_attribute("recipient", info.sender)
+        .add_attribute("token_id", token_id))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::FungibleEscrow { token, owner } => {
+            let token = deps.api.addr_validate(&token)?;
+            let owner = deps.api.addr_validate(&owner)?;
+            let amount = FUNGIBLE_ESCROW
+                .may_load(deps.storage, (&token, &owner))?
+                .unwrap_or(Uint128::zero());
+            to_json_binary(&FungibleEscrowResponse { amount })
+        }
+        QueryMsg::NftEscrow { token, token_id } => {
+            let token = deps.api.addr_validate(&token)?;
+            let owner = NFT_ESCROW
+                .may_load(deps.storage, (&token, &token_id))?
+                .map(|a| a.into_string());
+            to_json_binary(&NftEscrowResponse { owner })
+        }
+    }
+}
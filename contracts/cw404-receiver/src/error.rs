@@ -0,0 +1,17 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Receive rejected by the receiver")]
+    Rejected {},
+
+    #[error("Nothing escrowed for {owner}")]
+    NothingEscrowed { owner: String },
+
+    #[error("Token {token_id} is not escrowed for the sender")]
+    NotEscrowed { token_id: String },
+}
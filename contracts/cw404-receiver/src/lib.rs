@@ -0,0 +1,11 @@
+pub mod contract;
+pub mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
+pub use crate::msg::{ExecuteMsg, InnerMsg, InstantiateMsg, QueryMsg};
+
+// Version info for migration
+pub const CONTRACT_NAME: &str = "beepx:cw404-receiver";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
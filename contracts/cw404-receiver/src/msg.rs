@@ -0,0 +1,52 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Called by a cw404 contract after `Send`
+    Receive(Cw20ReceiveMsg),
+    /// Called by a cw404 contract after `SendNft`
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Return every fungible unit escrowed for the sender on `token`
+    ReleaseFungible { token: String },
+    /// Return an escrowed NFT to the sender that sent it
+    ReleaseNft { token: String, token_id: String },
+}
+
+/// Payload expected in the `msg` field of `Send` and `SendNft`.
+/// It decides how the receiver reacts to the incoming tokens.
+#[cw_serde]
+pub enum InnerMsg {
+    /// Accept the tokens and keep them
+    Succeed {},
+    /// Return an error, reverting the whole transfer
+    Fail {},
+    /// Accept the tokens and hold them for the sender until released
+    Escrow {},
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Fungible units escrowed for `owner` on `token`
+    #[returns(FungibleEscrowResponse)]
+    FungibleEscrow { token: String, owner: String },
+    /// Sender an NFT of `token` is escrowed for, if any
+    #[returns(NftEscrowResponse)]
+    NftEscrow { token: String, token_id: String },
+}
+
+#[cw_serde]
+pub struct FungibleEscrowResponse {
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct NftEscrowResponse {
+    pub owner: Option<String>,
+}
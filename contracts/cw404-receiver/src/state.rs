@@ -0,0 +1,8 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::Map;
+
+/// Fungible units held on behalf of the original sender, keyed by (token contract, sender)
+pub const FUNGIBLE_ESCROW: Map<(&Addr, &Addr), Uint128> = Map::new("fungible_escrow");
+
+/// Original sender of every escrowed NFT, keyed by (token contract, token id)
+pub const NFT_ESCROW: Map<(&Addr, &str), Addr> = Map::new("nft_escrow");
@@ -0,0 +1,364 @@
+use cosmwasm_std::{to_json_binary, Addr, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw721::OwnerOfResponse;
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+use cw404::{Cw404ExecuteMsg, Cw404InstantiateMsg, Cw404QueryMsg};
+use cw404_receiver::msg::{FungibleEscrowResponse, NftEscrowResponse};
+use cw404_receiver::{ContractError, ExecuteMsg, InnerMsg, InstantiateMsg, QueryMsg};
+
+const OWNER: &str = "owner";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+
+/// One whole token with 6 decimals
+const UNIT: u128 = 1_000_000;
+
+fn cw404_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        cw404_base::execute,
+        cw404_base::instantiate,
+        cw404_base::query,
+    ))
+}
+
+fn receiver_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        cw404_receiver::contract::execute,
+        cw404_receiver::contract::instantiate,
+        cw404_receiver::contract::query,
+    ))
+}
+
+struct Suite {
+    app: App,
+    token: Addr,
+    receiver: Addr,
+}
+
+/// Instantiates both contracts and gives Alice three whole tokens, i.e. NFTs 1, 2 and 3.
+fn setup() -> Suite {
+    let mut app = App::default();
+    let owner = Addr::unchecked(OWNER);
+
+    let token_id = app.store_code(cw404_contract());
+    let token = app
+        .instantiate_contract(
+            token_id,
+            owner.clone(),
+            &Cw404InstantiateMsg {
+                name: "Beep".to_string(),
+                symbol: "BEEP".to_string(),
+                decimals: 6,
+                total_native_supply: Uint128::new(100),
+                minter: None,
+                initial_balances: vec![Cw20Coin {
+                    address: ALICE.to_string(),
+                    amount: Uint128::new(3 * UNIT),
+                }],
+                sale_inventory: None,
+            },
+            &[],
+            "cw404",
+            None,
+        )
+        .unwrap();
+
+    let receiver_id = app.store_code(receiver_contract());
+    let receiver = app
+        .instantiate_contract(
+            receiver_id,
+            owner.clone(),
+            &InstantiateMsg {},
+            &[],
+            "receiver",
+            None,
+        )
+        .unwrap();
+
+    Suite {
+        app,
+        token,
+        receiver,
+    }
+}
+
+impl Suite {
+    fn send(&mut self, amount: u128, inner: InnerMsg) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(ALICE),
+            self.token.clone(),
+            &Cw404ExecuteMsg::<Empty>::Send {
+                contract: self.receiver.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(&inner).unwrap(),
+            },
+            &[],
+        )
+    }
+
+    fn send_nft(&mut self, token_id: &str, inner: InnerMsg) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(ALICE),
+            self.token.clone(),
+            &Cw404ExecuteMsg::<Empty>::SendNft {
+                contract: self.receiver.to_string(),
+                token_id: token_id.to_string(),
+                msg: to_json_binary(&inner).unwrap(),
+            },
+            &[],
+        )
+    }
+
+    fn release(&mut self, sender: &str, msg: ExecuteMsg) -> anyhow::Result<AppResponse> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.receiver.clone(), &msg, &[])
+    }
+
+    fn balance(&self, address: &str) -> u128 {
+        let res: cw20::BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.token,
+                &Cw404QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        res.balance.u128()
+    }
+
+    fn owner_of(&self, token_id: &str) -> String {
+        let res: OwnerOfResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.token,
+                &Cw404QueryMsg::OwnerOf {
+                    token_id: token_id.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        res.owner
+    }
+
+    fn nft_count(&self, owner: &str) -> usize {
+        let res: cw721::TokensResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.token,
+                &Cw404QueryMsg::Tokens {
+                    owner: owner.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.tokens.len()
+    }
+
+    fn fungible_escrow(&self, owner: &str) -> u128 {
+        let res: FungibleEscrowResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.receiver,
+                &QueryMsg::FungibleEscrow {
+                    token: self.token.to_string(),
+                    owner: owner.to_string(),
+                },
+            )
+            .unwrap();
+        res.amount.u128()
+    }
+
+    fn nft_escrow(&self, token_id: &str) -> Option<String> {
+        let res: NftEscrowResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.receiver,
+                &QueryMsg::NftEscrow {
+                    token: self.token.to_string(),
+                    token_id: token_id.to_string(),
+                },
+            )
+            .unwrap();
+        res.owner
+    }
+}
+
+#[test]
+fn send_accepted() {
+    let mut suite = setup();
+
+    suite.send(UNIT, InnerMsg::Succeed {}).unwrap();
+
+    assert_eq!(suite.balance(ALICE), 2 * UNIT);
+    assert_eq!(suite.balance(suite.receiver.as_str()), UNIT);
+    assert_eq!(suite.nft_count(ALICE), 2);
+    assert_eq!(suite.nft_count(suite.receiver.as_str()), 1);
+    assert_eq!(suite.fungible_escrow(ALICE), 0);
+}
+
+#[test]
+fn send_rejected_reverts_transfer() {
+    let mut suite = setup();
+
+    let err = suite.send(UNIT, InnerMsg::Fail {}).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::Rejected {}.to_string()
+    );
+
+    assert_eq!(suite.balance(ALICE), 3 * UNIT);
+    assert_eq!(suite.balance(suite.receiver.as_str()), 0);
+    assert_eq!(suite.nft_count(ALICE), 3);
+    assert_eq!(suite.nft_count(suite.receiver.as_str()), 0);
+}
+
+#[test]
+fn send_escrowed_and_released() {
+    let mut suite = setup();
+
+    suite.send(UNIT, InnerMsg::Escrow {}).unwrap();
+    assert_eq!(suite.fungible_escrow(ALICE), UNIT);
+    assert_eq!(suite.balance(suite.receiver.as_str()), UNIT);
+
+    // Only the original sender has anything to release
+    let err = suite
+        .release(
+            BOB,
+            ExecuteMsg::ReleaseFungible {
+                token: suite.token.to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NothingEscrowed {
+            owner: BOB.to_string()
+        }
+    );
+
+    suite
+        .release(
+            ALICE,
+            ExecuteMsg::ReleaseFungible {
+                token: suite.token.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.fungible_escrow(ALICE), 0);
+    assert_eq!(suite.balance(ALICE), 3 * UNIT);
+    assert_eq!(suite.balance(suite.receiver.as_str()), 0);
+    assert_eq!(suite.nft_count(ALICE), 3);
+    assert_eq!(suite.nft_count(suite.receiver.as_str()), 0);
+}
+
+#[test]
+fn send_nft_accepted() {
+    let mut suite = setup();
+
+    suite.send_nft("3", InnerMsg::Succeed {}).unwrap();
+
+    assert_eq!(suite.owner_of("3"), suite.receiver.to_string());
+    assert_eq!(suite.balance(ALICE), 2 * UNIT);
+    assert_eq!(suite.balance(suite.receiver.as_str()), UNIT);
+    assert_eq!(suite.nft_escrow("3"), None);
+}
+
+#[test]
+fn send_nft_rejected_reverts_transfer() {
+    let mut suite = setup();
+
+    let err = suite.send_nft("3", InnerMsg::Fail {}).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::Rejected {}.to_string()
+    );
+
+    assert_eq!(suite.owner_of("3"), ALICE);
+    assert_eq!(suite.balance(ALICE), 3 * UNIT);
+    assert_eq!(suite.balance(suite.receiver.as_str()), 0);
+}
+
+#[test]
+fn send_nft_escrowed_and_released() {
+    let mut suite = setup();
+
+    suite.send_nft("3", InnerMsg::Escrow {}).unwrap();
+    assert_eq!(suite.owner_of("3"), suite.receiver.to_string());
+    assert_eq!(suite.nft_escrow("3"), Some(ALICE.to_string()));
+
+    let err = suite
+        .release(
+            BOB,
+            ExecuteMsg::ReleaseNft {
+                token: suite.token.to_string(),
+                token_id: "3".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotEscrowed {
+            token_id: "3".to_string()
+        }
+    );
+
+    suite
+        .release(
+            ALICE,
+            ExecuteMsg::ReleaseNft {
+                token: suite.token.to_string(),
+                token_id: "3".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.owner_of("3"), ALICE);
+    assert_eq!(suite.nft_escrow("3"), None);
+    assert_eq!(suite.balance(ALICE), 3 * UNIT);
+    assert_eq!(suite.balance(suite.receiver.as_str()), 0);
+}
+
+#[test]
+fn fungible_release_keeps_escrowed_nft() {
+    let mut suite = setup();
+
+    suite.send(3 * UNIT / 2, InnerMsg::Escrow {}).unwrap();
+    suite.send_nft("1", InnerMsg::Escrow {}).unwrap();
+    let receiver = suite.receiver.to_string();
+    assert_eq!(suite.balance(&receiver), 5 * UNIT / 2);
+    assert_eq!(suite.nft_count(&receiver), 2);
+
+    // Dropping to one whole unit burns the other NFT, not the escrowed one
+    suite
+        .release(
+            ALICE,
+            ExecuteMsg::ReleaseFungible {
+                token: suite.token.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.balance(&receiver), UNIT);
+    assert_eq!(suite.owner_of("1"), receiver);
+    assert_eq!(suite.nft_count(&receiver), 1);
+
+    suite
+        .release(
+            ALICE,
+            ExecuteMsg::ReleaseNft {
+                token: suite.token.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.owner_of("1"), ALICE);
+    assert_eq!(suite.nft_escrow("1"), None);
+    assert_eq!(suite.balance(ALICE), 3 * UNIT);
+    assert_eq!(suite.balance(&receiver), 0);
+}
@@ -0,0 +1,288 @@
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use cosmwasm_std::{Addr, CustomMsg, Empty, Uint128};
+use cw20::{Logo, MarketingInfoResponse};
+use cw_storage_plus::{IndexedMap, Item, Map, MultiIndex, SnapshotItem, SnapshotMap, Strategy};
+use cw_utils::Expiration;
+
+use crate::msg::{ContractInfoResponse, FeeConfig, HookFailurePolicy, LaunchConfig, Payout};
+use crate::state::{token_owner_idx, TokenIndexes, TokenInfo};
+
+/// Storage namespaces used by [`Cw404Contract`].
+///
+/// The defaults match the layout of the standalone contract. Contracts that embed
+/// cw404 with the `library` feature can override individual keys to keep their own
+/// state separate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageKeys<'a> {
+    pub merkle_root: &'a str,
+    pub withdraw_address: &'a str,
+    pub name: &'a str,
+    pub symbol: &'a str,
+    pub tier_uris: &'a str,
+    pub decimals: &'a str,
+    pub total_supply: &'a str,
+    pub total_supply_checkpoints: &'a str,
+    pub total_supply_changelog: &'a str,
+    pub minted: &'a str,
+    pub whitelist: &'a str,
+    pub get_approved: &'a str,
+    pub allowance: &'a str,
+    pub balances: &'a str,
+    pub balances_checkpoints: &'a str,
+    pub balances_changelog: &'a str,
+    pub owner_of: &'a str,
+    pub owned: &'a str,
+    pub owned_index: &'a str,
+    pub burn_order: &'a str,
+    pub approved_for_all: &'a str,
+    pub contract_info: &'a str,
+    pub tokens: &'a str,
+    pub tokens_owner: &'a str,
+    pub locked: &'a str,
+    pub tier_extensions: &'a str,
+    pub token_tiers: &'a str,
+    pub roles: &'a str,
+    pub payouts: &'a str,
+    pub paused: &'a str,
+    pub launch_config: &'a str,
+    pub launch_allowlist: &'a str,
+    pub pools: &'a str,
+    pub blocklist: &'a str,
+    pub fee_config: &'a str,
+    pub fee_exempt: &'a str,
+    pub metadata_frozen: &'a str,
+    pub sale_inventory: &'a str,
+    pub marketing_info: &'a str,
+    pub logo: &'a str,
+    pub holder_count: &'a str,
+    pub nft_holder_count: &'a str,
+    pub burned: &'a str,
+    pub tier_counts: &'a str,
+    pub exempt_supply: &'a str,
+    pub hooks: &'a str,
+    pub nft_counts: &'a str,
+    pub nft_counts_checkpoints: &'a str,
+    pub nft_counts_changelog: &'a str,
+}
+
+impl Default for StorageKeys<'static> {
+    fn default() -> Self {
+        Self {
+            merkle_root: "merkle_root",
+            withdraw_address: "withdraw_address",
+            name: "name",
+            symbol: "symbol",
+            tier_uris: "tier_uris",
+            decimals: "decimals",
+            total_supply: "total_supply",
+            total_supply_checkpoints: "total_supply__checkpoints",
+            total_supply_changelog: "total_supply__changelog",
+            minted: "minted",
+            whitelist: "whitelist",
+            get_approved: "get_approved",
+            allowance: "cw20_allowance",
+            balances: "balance",
+            balances_checkpoints: "balance__checkpoints",
+            balances_changelog: "balance__changelog",
+            owner_of: "owner_of",
+            owned: "owned",
+            owned_index: "owned_index",
+            burn_order: "burn_order",
+            approved_for_all: "approved_for_all",
+            contract_info: "collection_info",
+            tokens: "tokens",
+            tokens_owner: "tokens__owner",
+            locked: "locked",
+            tier_extensions: "tier_extensions",
+            token_tiers: "token_tiers",
+            roles: "roles",
+            payouts: "payouts",
+            paused: "paused",
+            launch_config: "launch_config",
+            launch_allowlist: "launch_allowlist",
+            pools: "pools",
+            blocklist: "blocklist",
+            fee_config: "fee_config",
+            fee_exempt: "fee_exempt",
+            metadata_frozen: "metadata_frozen",
+            sale_inventory: "sale_inventory",
+            marketing_info: "marketing_info",
+            logo: "logo",
+            holder_count: "holder_count",
+            nft_holder_count: "nft_holder_count",
+            burned: "burned",
+            tier_counts: "tier_counts",
+            exempt_supply: "exempt_supply",
+            hooks: "hooks",
+            nft_counts: "nft_count",
+            nft_counts_checkpoints: "nft_count__checkpoints",
+            nft_counts_changelog: "nft_count__changelog",
+        }
+    }
+}
+
+pub struct Cw404Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub merkle_root: Item<'a, String>,
+    pub withdraw_address: Item<'a, String>,
+    pub name: Item<'a, String>,
+    pub symbol: Item<'a, String>,
+    /// Token URI of each tier set by `SetBaseTokenUri`, replacing the built-in one
+    pub tier_uris: Map<'a, u8, String>,
+    pub decimals: Item<'a, u8>,
+    /// In base units, with its value at every past height
+    pub total_supply: SnapshotItem<'a, Uint128>,
+    pub minted: Item<'a, Uint128>,
+    pub whitelist: Map<'a, String, bool>,
+    /// Approval in native representation
+    pub get_approved: Map<'a, String, String>,
+    /// Allowance of user in fractional representation
+    pub allowance: Map<'a, (String, String), Uint128>,
+    /// With the value of every balance at every past height
+    pub balances: SnapshotMap<'a, &'a Addr, Uint128>,
+    /// Owner of a tokenID in native representation
+    pub owner_of: Map<'a, String, String>,
+    /// Array of owned ids in native representation
+    pub owned: Map<'a, String, Vec<Uint128>>,
+    /// @dev Tracks indices for the _owned mapping
+    pub owned_index: Map<'a, String, Uint128>,
+    /// Ids an owner wants burned first, in native representation
+    pub burn_order: Map<'a, String, Vec<Uint128>>,
+    pub approved_for_all: Map<'a, (String, String), bool>,
+    /// Not under `contract_info`, which holds the `cw2` version
+    pub contract_info: Item<'a, ContractInfoResponse>,
+    pub tokens: IndexedMap<'a, &'a str, TokenInfo<T>, TokenIndexes<'a, T>>,
+    pub locked: Map<'a, String, bool>,
+    /// Extension copied onto every NFT minted in a tier
+    pub tier_extensions: Map<'a, u8, T>,
+    /// Tier chosen for a tokenID when it was minted
+    pub token_tiers: Map<'a, String, u8>,
+    /// Holders of each role, keyed by (role, address)
+    pub roles: Map<'a, (&'a str, &'a Addr), Empty>,
+    /// Withdrawal split, used instead of `withdraw_address` when set
+    pub payouts: Item<'a, Vec<Payout>>,
+    /// Paused operations and when each pause lifts itself
+    pub paused: Map<'a, &'a str, Expiration>,
+    pub launch_config: Item<'a, LaunchConfig>,
+    /// Senders allowed to transfer before trading starts
+    pub launch_allowlist: Map<'a, &'a Addr, Empty>,
+    /// Registered liquidity pools
+    pub pools: Map<'a, &'a Addr, Empty>,
+    /// Addresses barred from sending, receiving, approving and minting
+    pub blocklist: Map<'a, &'a Addr, Empty>,
+    pub fee_config: Item<'a, FeeConfig>,
+    /// Addresses whose transfers are never charged a fee
+    pub fee_exempt: Map<'a, &'a Addr, Empty>,
+    /// Once set, neither tier nor token metadata can be changed again
+    pub metadata_frozen: Item<'a, bool>,
+    /// Holder of the unallocated supply, which `NativeMint` sells from
+    pub sale_inventory: Item<'a, Addr>,
+    /// cw20 marketing info. `marketing` is left empty, the role holders are
+    /// read from `roles`
+    pub marketing_info: Item<'a, MarketingInfoResponse>,
+    pub logo: Item<'a, Logo>,
+    /// Addresses with a non-zero balance
+    pub holder_count: Item<'a, u64>,
+    /// Addresses owning at least one NFT
+    pub nft_holder_count: Item<'a, u64>,
+    /// NFTs burned so far; `minted - burned` are live
+    pub burned: Item<'a, Uint128>,
+    /// Live NFTs per tier
+    pub tier_counts: Map<'a, u8, u64>,
+    /// Sum of the balances of whitelisted addresses, which no NFTs back
+    pub exempt_supply: Item<'a, Uint128>,
+    /// Contracts sent a `Cw404HookMsg` on transfers, mints and burns
+    pub hooks: Map<'a, &'a Addr, HookFailurePolicy>,
+    /// NFTs owned per address, with their value at every past height
+    pub nft_counts: SnapshotMap<'a, &'a Addr, u32>,
+
+    pub(crate) _custom_response: PhantomData<C>,
+}
+
+impl<T, C> Default for Cw404Contract<'static, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    fn default() -> Self {
+        Self::new(StorageKeys::default())
+    }
+}
+
+impl<'a, T, C> Cw404Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn new(keys: StorageKeys<'a>) -> Self {
+        let indexes = TokenIndexes {
+            owner: MultiIndex::new(token_owner_idx, keys.tokens, keys.tokens_owner),
+        };
+        Self {
+            merkle_root: Item::new(keys.merkle_root),
+            withdraw_address: Item::new(keys.withdraw_address),
+            name: Item::new(keys.name),
+            symbol: Item::new(keys.symbol),
+            tier_uris: Map::new(keys.tier_uris),
+            decimals: Item::new(keys.decimals),
+            total_supply: SnapshotItem::new(
+                keys.total_supply,
+                keys.total_supply_checkpoints,
+                keys.total_supply_changelog,
+                Strategy::EveryBlock,
+            ),
+            minted: Item::new(keys.minted),
+            whitelist: Map::new(keys.whitelist),
+            get_approved: Map::new(keys.get_approved),
+            allowance: Map::new(keys.allowance),
+            balances: SnapshotMap::new(
+                keys.balances,
+                keys.balances_checkpoints,
+                keys.balances_changelog,
+                Strategy::EveryBlock,
+            ),
+            owner_of: Map::new(keys.owner_of),
+            owned: Map::new(keys.owned),
+            owned_index: Map::new(keys.owned_index),
+            burn_order: Map::new(keys.burn_order),
+            approved_for_all: Map::new(keys.approved_for_all),
+            contract_info: Item::new(keys.contract_info),
+            tokens: IndexedMap::new(keys.tokens, indexes),
+            locked: Map::new(keys.locked),
+            tier_extensions: Map::new(keys.tier_extensions),
+            token_tiers: Map::new(keys.token_tiers),
+            roles: Map::new(keys.roles),
+            payouts: Item::new(keys.payouts),
+            paused: Map::new(keys.paused),
+            launch_config: Item::new(keys.launch_config),
+            launch_allowlist: Map::new(keys.launch_allowlist),
+            pools: Map::new(keys.pools),
+            blocklist: Map::new(keys.blocklist),
+            fee_config: Item::new(keys.fee_config),
+            fee_exempt: Map::new(keys.fee_exempt),
+            metadata_frozen: Item::new(keys.metadata_frozen),
+            sale_inventory: Item::new(keys.sale_inventory),
+            marketing_info: Item::new(keys.marketing_info),
+            logo: Item::new(keys.logo),
+            holder_count: Item::new(keys.holder_count),
+            nft_holder_count: Item::new(keys.nft_holder_count),
+            burned: Item::new(keys.burned),
+            tier_counts: Map::new(keys.tier_counts),
+            exempt_supply: Item::new(keys.exempt_supply),
+            hooks: Map::new(keys.hooks),
+            nft_counts: SnapshotMap::new(
+                keys.nft_counts,
+                keys.nft_counts_checkpoints,
+                keys.nft_counts_changelog,
+                Strategy::EveryBlock,
+            ),
+            _custom_response: PhantomData,
+        }
+    }
+}
@@ -1,5 +1,4 @@
-use cosmwasm_std::StdError;
-use cw_ownable::OwnershipError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -7,29 +6,38 @@ pub enum ContractError {
     #[error(transparent)]
     Std(#[from] StdError),
 
-    #[error(transparent)]
-    Ownership(#[from] OwnershipError),
-
     #[error(transparent)]
     Version(#[from] cw2::VersionError),
 
     #[error("token_id already claimed")]
     Claimed {},
-   
-    #[error("Unauthorized")]
-    Unauthorized {},
-    
-    #[error("InvalidSender")]
-    InvalidSender {},
-    
-    #[error("InvalidRecipient")]
-    InvalidRecipient {},
-    
-    #[error("AlreadyExists")]
-    AlreadyExists {},
-
-    #[error("PreventBurn")]
-    PreventBurn {},
+
+    #[error("{address} may not manage token {token_id}")]
+    Unauthorized { address: String, token_id: String },
+
+    #[error("Cannot burn from invalid address '{address}'")]
+    InvalidSender { address: String },
+
+    #[error("{address} does not own token {token_id}")]
+    NotTokenOwner { token_id: String, address: String },
+
+    #[error("Cannot mint to invalid address '{address}'")]
+    InvalidRecipient { address: String },
+
+    #[error("Whitelisted address {address} cannot receive NFTs")]
+    WhitelistedRecipient { address: String },
+
+    #[error("Token {token_id} already exists")]
+    AlreadyExists { token_id: String },
+
+    #[error("No unlocked NFT of {owner} can be burned; unlock one or transfer less")]
+    PreventBurn { owner: String },
+
+    #[error("Invalid token id: {token_id}")]
+    InvalidTokenId { token_id: String },
+
+    #[error("Token not found: {token_id}")]
+    TokenNotFound { token_id: String },
 
     #[error("Cannot set approval that is already expired")]
     Expired {},
@@ -43,12 +51,122 @@ pub enum ContractError {
     #[error("Invalid input!")]
     InvalidInput {},
 
-    #[error("Not enough token balance!")]
-    InsufficientBalance {},
-    
-    #[error("Incorrect amount!")]
-    IncorrectAmount {},
+    #[error("{address} holds {balance}, cannot move {amount}")]
+    InsufficientBalance {
+        address: String,
+        balance: Uint128,
+        amount: Uint128,
+    },
+
+    #[error("{spender} may spend {allowance} of {owner}, cannot move {amount}")]
+    InsufficientAllowance {
+        owner: String,
+        spender: String,
+        allowance: Uint128,
+        amount: Uint128,
+    },
+
+    #[error("Expected {expected} {denom}, received {received}")]
+    IncorrectAmount {
+        denom: String,
+        expected: Uint128,
+        received: Uint128,
+    },
+
+    #[error("Sale inventory {address} must stay whitelisted")]
+    SaleInventoryWhitelist { address: String },
+
+    #[error("Invalid tier: {tier}")]
+    InvalidTier { tier: u8 },
+
+    #[error("Metadata is frozen")]
+    MetadataFrozen {},
+
+    #[error("Contract info is frozen")]
+    ContractInfoFrozen {},
+
+    #[error("Logo of {size} bytes exceeds the limit of {max} bytes")]
+    LogoTooBig { size: usize, max: usize },
+
+    #[error("Invalid xml preamble for SVG")]
+    InvalidXmlPreamble {},
+
+    #[error("Invalid png header")]
+    InvalidPngHeader {},
 
     #[error("Not whitelisted! wait for public")]
     NotWhitelisted {},
+
+    #[error("{address} does not hold the {role} role")]
+    MissingRole { role: String, address: String },
+
+    #[error("Cannot revoke the last admin")]
+    LastAdmin {},
+
+    #[error("Payout shares must add up to 10000 bps, got {total_bps}")]
+    InvalidPayouts { total_bps: u32 },
+
+    #[error("Contract holds no funds to withdraw")]
+    NoFunds {},
+
+    #[error("{target} is paused")]
+    Paused { target: String },
+
+    #[error("Pause must end after the current block, not at height {height}")]
+    InvalidPauseHeight { height: u64 },
+
+    #[error("Amount {amount} does not cover the listed NFTs, which need {required}")]
+    AmountBelowTokenIds { amount: Uint128, required: Uint128 },
+
+    #[error("Fee of {bps} bps exceeds 10000 bps")]
+    InvalidFee { bps: u16 },
+
+    #[error("Address {address} is blocked")]
+    Blocked { address: String },
+
+    #[error("Trading has not started yet")]
+    TradingNotStarted {},
+
+    #[error("Transfer of {amount} exceeds the launch limit of {max} per transaction")]
+    MaxTxExceeded { amount: Uint128, max: Uint128 },
+
+    #[error("Balance of {address} would reach {balance}, above the launch limit of {max}")]
+    MaxWalletExceeded {
+        address: String,
+        balance: Uint128,
+        max: Uint128,
+    },
+
+    #[error("Name must be 3 to 50 characters long, got {name:?}")]
+    InvalidName { name: String },
+
+    #[error("Symbol must be 3 to 12 letters or dashes, got {symbol:?}")]
+    InvalidSymbol { symbol: String },
+
+    #[error("Decimals must not exceed 18, got {decimals}")]
+    InvalidDecimals { decimals: u8 },
+
+    #[error("Supply of {total_native_supply} with {decimals} decimals overflows")]
+    SupplyOverflow {
+        total_native_supply: Uint128,
+        decimals: u8,
+    },
+
+    #[error("Initial balances of {total} exceed the total supply of {supply}")]
+    InitialBalancesExceedSupply { total: Uint128, supply: Uint128 },
+
+    #[error("Duplicate initial balance for {address}")]
+    DuplicateInitialBalance { address: String },
+
+    #[error("Cannot register more than {max} hooks")]
+    TooManyHooks { max: usize },
+
+    #[error("Unknown reply id {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Invalid contract version: {version}")]
+    InvalidVersion { version: String },
+
+    #[error("Cannot migrate from newer version ({stored}) to older ({new})")]
+    CannotDowngrade { stored: String, new: String },
 }
@@ -1,815 +1,2695 @@
 use std::str::FromStr;
 
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
 use cosmwasm_std::{
-    to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Storage, Uint128, WasmMsg
+    to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, CustomMsg, DepsMut, Empty, Env, Event,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, SubMsgResult,
+    Uint128, WasmMsg,
 };
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
-use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
-use cw_ownable::get_ownership;
-use sha3::{Digest, Keccak256};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, EmbeddedLogo, Logo, LogoInfo};
+use cw721::{Cw721Execute, Cw721ExecuteMsg, Cw721ReceiveMsg};
+use cw_storage_plus::Item;
+use cw_utils::Expiration;
 
+use crate::contract::Cw404Contract;
 use crate::error::ContractError;
-use crate::msg::{ContractInfoResponse, ExecuteMsg, InstantiateMsg};
-use crate::state::{
-    TokenInfo, ALLOWANCE, APPROVED_FOR_ALL, BALANCES, CONTRACT_INFO, DECIMALS, GET_APPROVED, LOCKED, MERKLE_ROOT, MINTED, NAME, OWNED, OWNED_INDEX, OWNER, OWNER_OF, SYMBOL, TOKENS, TOKEN_URI, TOTAL_SUPPLY, WHITELIST, WITHDRAW_ADDRESS
+use crate::msg::{
+    ContractInfoResponse, Cw404HookMsg, ExecuteMsg, FeeConfig, HookAction, HookFailurePolicy,
+    InstantiateMsg, LaunchConfig, MigrateMsg, PauseTarget, Payout, Role,
 };
+use crate::state::{Approval, TokenInfo, BPS_DENOMINATOR, HOOK_REPLY_ID, MAX_HOOKS, TIER_COUNT};
+use crate::upgrades;
+
+/// Token URI of each tier, indexed by `tier - 1`.
+pub(crate) const TIER_URIS: [&str; TIER_COUNT as usize] = [
+    "https://arweave.net/XpXSyZiPGlpcc-Dsz7XMwdxKeNuczW-01uR5rNqOj3w",
+    "https://arweave.net/FRlxtstfBtzB_ocR8l7iJhU1vHTgljm68iGefpWKs4I",
+    "https://arweave.net/er-LhktIb_jZBPwUIHX0MGSGpYMz6OAP03bk74teGRg",
+    "https://arweave.net/DpzV3S9E-7FhMKSkDAImKH8mVYgh26g5ka1rZggelr8",
+    "https://arweave.net/4vT1QhisR_8ENY9oCpz3X05qTLaMyPpkVQUSX6Ug6_I",
+];
+
+/// Outcome of a fungible transfer, see [`Cw404Contract::plan_transfer`].
+pub struct TransferPlan {
+    pub fee: Option<(Addr, Uint128)>,
+    /// What reaches the recipient after the fee
+    pub received: Uint128,
+    /// NFTs burned from the sender, in order
+    pub burn_ids: Vec<Uint128>,
+    /// NFTs minted to the recipient
+    pub mint_count: u128,
+}
 
-pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
-    let total_supply = msg.total_native_supply.u128() * ((10u128).pow(msg.decimals.into()));
-    DECIMALS.save(deps.storage, &msg.decimals)?;
-    TOTAL_SUPPLY.save(deps.storage, &Uint128::from(total_supply))?;
-    MINTED.save(deps.storage, &Uint128::zero())?;
-    NAME.save(deps.storage, &msg.name)?;
-    SYMBOL.save(deps.storage, &msg.symbol)?;
-    // MERKLE_ROOT.save(deps.storage, &"21afb4d04947e9028f7f7c6814be583f92292c032011e0ddf5b443035b699489".to_string())?;
-    OWNER.save(deps.storage, &info.sender.to_string())?;
-
-    BALANCES.save(deps.storage, &info.sender, &Uint128::from(total_supply))?;
-
-    let contract_info = ContractInfoResponse {
-        name: msg.name,
-        symbol: msg.symbol,
-        decimals: msg.decimals,
-        total_supply: msg.total_native_supply,
-    };
+/// Largest embedded logo accepted by `UploadLogo`, as in cw20-base.
+const LOGO_SIZE_CAP: usize = 5 * 1024;
+
+/// First bytes of every PNG file.
+const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+impl<'a, T, C> Cw404Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone + Default,
+    C: CustomMsg,
+{
+    /// Splits the supply between `initial_balances` and the sale inventory.
+    /// The sale inventory is whitelisted so its allocation does not mint NFTs,
+    /// while every other initial holder receives the NFTs its balance backs.
+    pub fn instantiate(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> Result<Response<C>, ContractError> {
+        validate_token_info(&msg)?;
+        let total_supply = Uint128::from(10u128)
+            .checked_pow(msg.decimals.into())
+            .and_then(|unit| msg.total_native_supply.checked_mul(unit))
+            .map_err(|_| ContractError::SupplyOverflow {
+                total_native_supply: msg.total_native_supply,
+                decimals: msg.decimals,
+            })?;
+        self.decimals.save(deps.storage, &msg.decimals)?;
+        self.total_supply
+            .save(deps.storage, &total_supply, env.block.height)?;
+        self.minted.save(deps.storage, &Uint128::zero())?;
+        self.burned.save(deps.storage, &Uint128::zero())?;
+        self.name.save(deps.storage, &msg.name)?;
+        self.symbol.save(deps.storage, &msg.symbol)?;
+        // self.merkle_root.save(deps.storage, &"21afb4d04947e9028f7f7c6814be583f92292c032011e0ddf5b443035b699489".to_string())?;
+
+        let owner = match msg.minter {
+            Some(owner) => deps.api.addr_validate(&owner)?,
+            None => info.sender,
+        };
+        let inventory = match msg.sale_inventory {
+            Some(inventory) => deps.api.addr_validate(&inventory)?,
+            None => owner.clone(),
+        };
+        self.sale_inventory.save(deps.storage, &inventory)?;
+        self.whitelist
+            .save(deps.storage, inventory.to_string(), &true)?;
+
+        let mut events = vec![];
+        let mut allocated = Uint128::zero();
+        for coin in &msg.initial_balances {
+            let address = deps.api.addr_validate(&coin.address)?;
+            if self.balances.may_load(deps.storage, &address)?.is_some() {
+                return Err(ContractError::DuplicateInitialBalance {
+                    address: coin.address.clone(),
+                });
+            }
+            allocated = allocated.checked_add(coin.amount).map_err(StdError::from)?;
+            let minted = self._credit(deps.storage, &env, &address, coin.amount)?;
+            events.extend(mint_events(&address, &minted));
+        }
+        let remaining = total_supply.checked_sub(allocated).map_err(|_| {
+            ContractError::InitialBalancesExceedSupply {
+                total: allocated,
+                supply: total_supply,
+            }
+        })?;
+        if !remaining.is_zero() {
+            self._credit(deps.storage, &env, &inventory, remaining)?;
+        }
 
-    CONTRACT_INFO.save(deps.storage, &contract_info)?;
+        let contract_info = ContractInfoResponse {
+            name: msg.name,
+            symbol: msg.symbol,
+            decimals: msg.decimals,
+            total_supply: msg.total_native_supply,
+            description: None,
+            image: None,
+            external_link: None,
+            frozen: false,
+        };
 
-    let owner = match msg.minter {
-        Some(owner) => deps.api.addr_validate(&owner)?,
-        None => info.sender,
-    };
-    cw_ownable::initialize_owner(deps.storage, deps.api, Some(owner.as_ref()))?;
+        self.contract_info.save(deps.storage, &contract_info)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "mint")
-        .add_attribute("to", owner.to_string())
-        .add_attribute("amount", total_supply.to_string()))
-}
+        for role in Role::ALL {
+            self.roles
+                .save(deps.storage, (role.as_str(), &owner), &Empty {})?;
+        }
 
-pub fn execute(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    msg: ExecuteMsg,
-) -> Result<Response, ContractError> {
-    match msg {
-        ExecuteMsg::Mint {
-            token_id,
-            owner,
-            token_uri,
-            extension,
-        } => mint(deps, info, token_id, owner, token_uri, extension),
-        ExecuteMsg::Burn { token_id } => burn(deps, env, info, token_id),
-        ExecuteMsg::Receive(msg) => try_receive_cw20(deps,env, info, msg),
-        ExecuteMsg::NativeMint{recipient, merkle_proof, hashed_address}=> try_receive_native_tokens(deps, env,info, recipient, merkle_proof, hashed_address),
-        ExecuteMsg::ReceiveNft(msg) => try_receive_cw721(deps, env, info, msg),
-        ExecuteMsg::Approve { spender, token_id } => approve(deps, env, info, spender, token_id),
-        ExecuteMsg::ApproveAll { operator } => approve_all(deps, env, info, operator),
-        ExecuteMsg::IncreaseAllowance {
-            spender,
-            amount,
-            expires: _expires,
-        } => approve(deps, env, info, spender, amount),
-        ExecuteMsg::RevokeAll { operator } => revoke_all(deps, env, info, operator),
-        // This is the default implementation in erc404
-        ExecuteMsg::TransferFrom {
-            owner,
-            recipient,
-            amount,
-        } => transfer_from(deps, env, info, owner, recipient, amount),
-        // This is the default implementation in erc404
-        ExecuteMsg::Transfer { recipient, amount } => transfer(
-            deps,
-            env,
-            info.clone(),
-            info.sender.to_string(),
-            recipient,
-            amount,
-        ),
-        // Added to ensure compatibility with cw721
-        ExecuteMsg::TransferNft {
-            recipient,
-            token_id,
-        } => transfer_from(
-            deps,
-            env,
-            info.clone(),
-            info.sender.to_string(),
-            recipient,
-            token_id,
-        ),
-        // Added to ensure compatibility with cw20
-        ExecuteMsg::Send {
-            contract,
-            amount,
-            msg,
-        } => send(
-            deps,
-            env,
-            info.clone(),
-            info.sender.to_string(),
-            contract,
-            msg,
-            amount,
-        ),
-        // Added to ensure compatibility with cw721
-        ExecuteMsg::SendNft {
-            contract,
-            token_id,
-            msg,
-        } => send(
-            deps,
-            env,
-            info.clone(),
-            info.sender.to_string(),
-            contract,
-            msg,
-            token_id,
-        ),
+        Ok(Response::new()
+            .add_attribute("action", "mint")
+            .add_attribute("to", inventory.to_string())
+            .add_attribute("amount", remaining.to_string())
+            .add_attribute("total_supply", total_supply.to_string())
+            .add_events(events))
+    }
+
+    /// Checks the stored cw2 version against `contract_version`, refusing other
+    /// contracts and downgrades, then runs every state upgrade the stored
+    /// version still needs.
+    pub fn migrate(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        _msg: MigrateMsg,
+        contract_name: &str,
+        contract_version: &str,
+    ) -> Result<Response<C>, ContractError> {
+        let stored = match cw2::CONTRACT.may_load(deps.storage) {
+            Ok(stored) => stored.ok_or(cw2::VersionError::NotFound)?,
+            Err(err) => upgrades::v0_1::stored_version(deps.storage, contract_name).ok_or(err)?,
+        };
+        if stored.contract != contract_name {
+            return Err(cw2::VersionError::WrongContract {
+                expected: contract_name.to_string(),
+                found: stored.contract,
+            }
+            .into());
+        }
+
+        let stored_version = parse_version(&stored.version)?;
+        let new_version = parse_version(contract_version)?;
+        if stored_version > new_version {
+            return Err(ContractError::CannotDowngrade {
+                stored: stored.version,
+                new: contract_version.to_string(),
+            });
+        }
 
-        ExecuteMsg::SetWithdrawAddress { address } => {
-            set_withdraw_address(deps, &info.sender, address)
-        },
-        ExecuteMsg::RemoveWithdrawAddress {} => {
-            remove_withdraw_address(deps.storage, &info.sender)
+        let mut changes = vec![];
+        if stored_version < semver::Version::new(0, 2, 0) {
+            changes.extend(upgrades::v0_1::migrate(
+                deps.storage,
+                env.block.height,
+                self,
+            )?);
         }
-        ExecuteMsg::WithdrawFunds { amount } => withdraw_funds(deps.storage, &amount),
-        // Auxillary functions
-        ExecuteMsg::SetWhitelist { target, state } => set_whitelist(deps, env, info, target, state),
-        ExecuteMsg::SetBaseTokenUri {id,  uri } => set_base_token_uri(deps, env, info, id, uri),
-        ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
+        cw2::set_contract_version(deps.storage, contract_name, contract_version)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("from_version", stored.version)
+            .add_attribute("to_version", contract_version)
+            .add_attributes(changes))
     }
-}
 
-fn try_receive_native_tokens(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    recipient: String,  
-    _merkle_proof: Vec<Vec<u8>>,
-    _hashed_address: Vec<u8>
-) -> Result<Response, ContractError> {
-    // let mut current_hash = hashed_address;
-    
-    // // Iterate over the proof, hashing the current hash with each proof element
-    // for p in merkle_proof {
-    //     let mut hasher = Keccak256::default();
-        
-    //     // Depending on the order, hash the concatenation of the current hash and the proof element
-    //     // Adjust this logic based on how your tree is structured (e.g., sorting pairs)
-    //     hasher.update(&[current_hash, p].concat());
-        
-    //     current_hash = hasher.finalize().to_vec();
-    // }
-
-    // // Retrieve the stored Merkle root
-    // let stored_root = MERKLE_ROOT.load(deps.storage)?;
-
-    // // Convert the current hash (calculated root) to a comparable format
-    // let calculated_root = hex::encode(current_hash);
-
-    // if stored_root != calculated_root {
-    //     return Err(ContractError::NotWhitelisted{});
-    // }
-
-    let received_amount = info.funds.iter().find(|coin| coin.denom == "usei").map_or(Uint128::zero(), |coin| coin.amount);
-    let decimals = get_unit(deps.storage).unwrap();
-    let required_amount=Uint128::new(50000000);
-    let token_amount = Uint128::new(1);
-    if received_amount.eq(&Uint128::from_str(&required_amount.to_string())?){
-        let owner= get_ownership(deps.storage).unwrap().owner.unwrap();
-        
-
-        let response = _transfer(
-            deps,
-            env,
-            info.clone(),
-            owner.to_string(),
-            recipient,
-            token_amount.checked_mul(decimals).expect("invalid"),
-            "transfer_from".to_string(),
-        )
-        .unwrap();
-    
-        Ok(response.add_attribute("by", info.sender))
-        // Ok(Response::new())
-    } else {
-        return Err(ContractError::IncorrectAmount {});
+    /// Records hook submessages that failed under [`HookFailurePolicy::Ignore`].
+    /// Their state changes are already reverted, the triggering operation stands.
+    pub fn reply(
+        &self,
+        _deps: DepsMut,
+        _env: Env,
+        msg: Reply,
+    ) -> Result<Response<C>, ContractError> {
+        match (msg.id, msg.result) {
+            (HOOK_REPLY_ID, SubMsgResult::Err(error)) => Ok(Response::new()
+                .add_attribute("action", "hook_failed")
+                .add_attribute("error", error)),
+            (id, _) => Err(ContractError::UnknownReplyId { id }),
+        }
     }
 
+    pub fn execute(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg<T>,
+    ) -> Result<Response<C>, ContractError> {
+        match msg {
+            ExecuteMsg::Mint {
+                token_id,
+                owner,
+                token_uri,
+                extension,
+            } => self.mint(deps, info, token_id, owner, token_uri, extension),
+            ExecuteMsg::Burn { token_id } => self.burn(deps, env, info, token_id),
+            ExecuteMsg::Receive(msg) => self.try_receive_cw20(deps, env, info, msg),
+            ExecuteMsg::NativeMint {
+                recipient,
+                merkle_proof,
+                hashed_address,
+            } => self.try_receive_native_tokens(
+                deps,
+                env,
+                info,
+                recipient,
+                merkle_proof,
+                hashed_address,
+            ),
+            ExecuteMsg::ReceiveNft(msg) => self.try_receive_cw721(deps, env, info, msg),
+            ExecuteMsg::Approve {
+                spender,
+                token_id,
+                expires,
+            } => {
+                let token_id = self.parse_token_id(deps.storage, &token_id)?;
+                self.approve_nft(deps, env, info, spender, token_id, expires)
+            }
+            ExecuteMsg::ApproveAll { operator } => self.approve_all(deps, env, info, operator),
+            ExecuteMsg::IncreaseAllowance {
+                spender,
+                amount,
+                expires: _expires,
+            } => self.increase_allowance(deps, env, info, spender, amount),
+            ExecuteMsg::RevokeAll { operator } => self.revoke_all(deps, env, info, operator),
+            // This is the default implementation in erc404
+            ExecuteMsg::TransferFrom {
+                owner,
+                recipient,
+                amount,
+            } => self.transfer_amount_or_id(deps, env, info, owner, recipient, amount),
+            // This is the default implementation in erc404
+            ExecuteMsg::Transfer { recipient, amount } => self.transfer(
+                deps,
+                env,
+                info.clone(),
+                info.sender.to_string(),
+                recipient,
+                amount,
+            ),
+            ExecuteMsg::TransferWithIds {
+                recipient,
+                amount,
+                token_ids,
+            } => self.transfer_with_ids(deps, env, info, recipient, amount, token_ids),
+            ExecuteMsg::SetBurnOrder { token_ids } => self.set_burn_order(deps, info, token_ids),
+            ExecuteMsg::Lock { token_id } => self.set_locked(deps, info, token_id, true),
+            ExecuteMsg::Unlock { token_id } => self.set_locked(deps, info, token_id, false),
+            // Added to ensure compatibility with cw721
+            ExecuteMsg::TransferNft {
+                recipient,
+                token_id,
+            } => {
+                let token_id = self.parse_token_id(deps.storage, &token_id)?;
+                self._transfer_nft(
+                    deps,
+                    env,
+                    info.clone(),
+                    info.sender.to_string(),
+                    recipient,
+                    token_id,
+                )
+            }
+            // Added to ensure compatibility with cw20
+            ExecuteMsg::Send {
+                contract,
+                amount,
+                msg,
+            } => self.send(
+                deps,
+                env,
+                info.clone(),
+                info.sender.to_string(),
+                contract,
+                msg,
+                amount,
+            ),
+            // Added to ensure compatibility with cw721
+            ExecuteMsg::SendNft {
+                contract,
+                token_id,
+                msg,
+            } => {
+                let token_id = self.parse_token_id(deps.storage, &token_id)?;
+                self.send_nft(deps, env, info, contract, msg, token_id)
+            }
+
+            ExecuteMsg::SetWithdrawAddress { address } => {
+                self.set_withdraw_address(deps, &info.sender, address)
+            }
+            ExecuteMsg::RemoveWithdrawAddress {} => {
+                self.remove_withdraw_address(deps.storage, &info.sender)
+            }
+            ExecuteMsg::WithdrawFunds { amount } => {
+                self.withdraw_funds(deps.storage, &info.sender, &amount)
+            }
+            ExecuteMsg::WithdrawAll {} => self.withdraw_all(deps, env, &info.sender),
+            ExecuteMsg::SetPayouts { payouts } => self.set_payouts(deps, &info.sender, payouts),
+            // Auxillary functions
+            ExecuteMsg::SetWhitelist { target, state } => {
+                self.set_whitelist(deps, env, info, target, state)
+            }
+            ExecuteMsg::SetBaseTokenUri { id, uri } => {
+                self.set_base_token_uri(deps, env, info, id, uri)
+            }
+            ExecuteMsg::Pause {
+                targets,
+                until_height,
+            } => self.pause(deps, env, info, targets, until_height),
+            ExecuteMsg::Unpause { targets } => self.unpause(deps, info, targets),
+            ExecuteMsg::SetLaunchConfig { config } => self.set_launch_config(deps, info, config),
+            ExecuteMsg::SetLaunchAllowlist { address, allowed } => {
+                self.set_launch_allowlist(deps, info, address, allowed)
+            }
+            ExecuteMsg::SetPool { address, is_pool } => self.set_pool(deps, info, address, is_pool),
+            ExecuteMsg::SetFeeConfig { config } => self.set_fee_config(deps, info, config),
+            ExecuteMsg::SetFeeExempt { address, exempt } => {
+                self.set_fee_exempt(deps, info, address, exempt)
+            }
+            ExecuteMsg::SetBlocklist { address, blocked } => {
+                self.set_blocklist(deps, info, address, blocked)
+            }
+            ExecuteMsg::GrantRole { role, address } => self.grant_role(deps, info, role, address),
+            ExecuteMsg::RevokeRole { role, address } => self.revoke_role(deps, info, role, address),
+            ExecuteMsg::AddHook { address, policy } => self.add_hook(deps, info, address, policy),
+            ExecuteMsg::RemoveHook { address } => self.remove_hook(deps, info, address),
+            ExecuteMsg::SetTierMetadata { tier, extension } => {
+                self.set_tier_metadata(deps, info, tier, extension)
+            }
+            ExecuteMsg::UpdateNftMetadata {
+                token_id,
+                extension,
+            } => self.update_nft_metadata(deps, info, token_id, extension),
+            ExecuteMsg::FreezeMetadata {} => self.freeze_metadata(deps, info),
+            ExecuteMsg::UpdateContractInfo {
+                name,
+                symbol,
+                description,
+                image,
+                external_link,
+            } => self.update_contract_info(
+                deps,
+                info,
+                name,
+                symbol,
+                description,
+                image,
+                external_link,
+            ),
+            ExecuteMsg::FreezeContractInfo {} => self.freeze_contract_info(deps, info),
+            ExecuteMsg::UpdateMarketing {
+                project,
+                description,
+                marketing,
+            } => self.update_marketing(deps, info, project, description, marketing),
+            ExecuteMsg::UploadLogo(logo) => self.upload_logo(deps, info, logo),
+        }
+    }
 }
 
+impl<'a, T, C> Cw721Execute<T, C> for Cw404Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone + Default,
+    C: CustomMsg,
+{
+    type Err = ContractError;
+
+    fn transfer_from(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        from: String,
+        to: String,
+        token_id: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        self._transfer_nft(deps, env, info, from, to, token_id)
+    }
 
+    fn approve(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        self.approve_nft(deps, env, info, spender, token_id, None)
+    }
 
-pub fn set_withdraw_address(
-    deps: DepsMut,
-    sender: &Addr,
-    address: String,
-) -> Result<Response, ContractError> {
-    cw_ownable::assert_owner(deps.storage, sender)?;
-    deps.api.addr_validate(&address)?;
-    WITHDRAW_ADDRESS.save(deps.storage, &address)?;
-    Ok(Response::new()
-        .add_attribute("action", "set_withdraw_address")
-        .add_attribute("address", address))
-}
+    fn approve_all(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        operator: String,
+    ) -> Result<Response<C>, ContractError> {
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        self.assert_not_blocked(deps.storage, &[&info.sender, &operator_addr])?;
+
+        self.approved_for_all.save(
+            deps.storage,
+            (info.sender.to_string(), operator.clone()),
+            &true,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "approve_all")
+            .add_attribute("sender", info.sender)
+            .add_attribute("operator", operator))
+    }
+
+    fn revoke_all(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        operator: String,
+    ) -> Result<Response<C>, ContractError> {
+        deps.api.addr_validate(&operator)?;
+
+        self.approved_for_all.save(
+            deps.storage,
+            (info.sender.to_string(), operator.clone()),
+            &false,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_all")
+            .add_attribute("sender", info.sender)
+            .add_attribute("operator", operator.to_string()))
+    }
+
+    //for v2
+    fn burn(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Minter, &info.sender)?;
+
+        // let token = self.tokens.load(deps.storage, &token_id)?;
+        // self.check_can_send(deps.as_ref(), &env, &info, &token)?;
 
-pub fn remove_withdraw_address(
-    storage: &mut dyn Storage,
-    sender: &Addr,
-) -> Result<Response, ContractError> {
-    cw_ownable::assert_owner(storage, sender)?;
-    let address = WITHDRAW_ADDRESS.may_load(storage)?;
-    match address {
-        Some(address) => {
-            WITHDRAW_ADDRESS.remove(storage);
-            Ok(Response::new()
-                .add_attribute("action", "remove_withdraw_address")
-                .add_attribute("address", address))
-        }
-        None => Err(ContractError::NoWithdrawAddress {}),
+        // self.tokens.remove(deps.storage, &token_id)?;
+        // self.decrement_tokens(deps.storage)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "burn")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id))
     }
 }
 
-pub fn withdraw_funds(
-    storage: &mut dyn Storage,
-    amount: &Coin,
-) -> Result<Response, ContractError> {
-    
-    let address = WITHDRAW_ADDRESS.may_load(storage)?;
-    match address {
-        Some(address) => {
-            let msg = BankMsg::Send {
-                to_address: address,
-                amount: vec![amount.clone()],
-            };
-            Ok(Response::new()
-                .add_message(msg)
-                .add_attribute("action", "withdraw_funds")
-                .add_attribute("amount", amount.amount.to_string())
-                .add_attribute("denom", amount.denom.to_string()))
+// helpers
+impl<'a, T, C> Cw404Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone + Default,
+    C: CustomMsg,
+{
+    pub fn try_receive_native_tokens(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        _merkle_proof: Vec<Vec<u8>>,
+        _hashed_address: Vec<u8>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_not_paused(deps.storage, &env, PauseTarget::NativeMint)?;
+        // let mut current_hash = hashed_address;
+
+        // // Iterate over the proof, hashing the current hash with each proof element
+        // for p in merkle_proof {
+        //     let mut hasher = Keccak256::default();
+
+        //     // Depending on the order, hash the concatenation of the current hash and the proof element
+        //     // Adjust this logic based on how your tree is structured (e.g., sorting pairs)
+        //     hasher.update(&[current_hash, p].concat());
+
+        //     current_hash = hasher.finalize().to_vec();
+        // }
+
+        // // Retrieve the stored Merkle root
+        // let stored_root = self.merkle_root.load(deps.storage)?;
+
+        // // Convert the current hash (calculated root) to a comparable format
+        // let calculated_root = hex::encode(current_hash);
+
+        // if stored_root != calculated_root {
+        //     return Err(ContractError::NotWhitelisted{});
+        // }
+
+        let received_amount = info
+            .funds
+            .iter()
+            .find(|coin| coin.denom == "usei")
+            .map_or(Uint128::zero(), |coin| coin.amount);
+        let decimals = self.get_unit(deps.storage)?;
+        let required_amount = Uint128::new(50000000);
+        let token_amount = Uint128::new(1);
+        if received_amount.eq(&Uint128::from_str(&required_amount.to_string())?) {
+            let owner = self.sale_inventory.load(deps.storage)?;
+
+            let response = self._transfer(
+                deps,
+                env,
+                info.clone(),
+                owner.to_string(),
+                recipient,
+                token_amount.checked_mul(decimals).map_err(StdError::from)?,
+                "transfer_from".to_string(),
+            )?;
+
+            Ok(response.add_attribute("by", info.sender))
+            // Ok(Response::new())
+        } else {
+            Err(ContractError::IncorrectAmount {
+                denom: "usei".to_string(),
+                expected: required_amount,
+                received: received_amount,
+            })
         }
-        None => Err(ContractError::NoWithdrawAddress {}),
     }
-}
 
+    pub fn set_withdraw_address(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        address: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Treasurer, sender)?;
+        deps.api.addr_validate(&address)?;
+        self.withdraw_address.save(deps.storage, &address)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_withdraw_address")
+            .add_attribute("address", address))
+    }
+
+    pub fn remove_withdraw_address(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(storage, Role::Treasurer, sender)?;
+        let address = self.withdraw_address.may_load(storage)?;
+        match address {
+            Some(address) => {
+                self.withdraw_address.remove(storage);
+                Ok(Response::new()
+                    .add_attribute("action", "remove_withdraw_address")
+                    .add_attribute("address", address))
+            }
+            None => Err(ContractError::NoWithdrawAddress {}),
+        }
+    }
 
-pub fn try_receive_cw721(
-    _deps: DepsMut,
-    env: Env,
-    _info: MessageInfo,
-    msg: Cw721ReceiveMsg,
+    pub fn withdraw_funds(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        amount: &Coin,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(storage, Role::Treasurer, sender)?;
+        let msgs = self.payout_msgs(storage, std::slice::from_ref(amount))?;
+        Ok(Response::new()
+            .add_messages(msgs)
+            .add_attribute("action", "withdraw_funds")
+            .add_attribute("amount", amount.amount.to_string())
+            .add_attribute("denom", amount.denom.to_string()))
+    }
 
-) -> Result<Response, ContractError> {
-    let forward_to_address = "sei1wpha09pxxmxcu0yvcrcsqew4payuhenpw0c642".to_string();
+    /// Sweeps every denom held by the contract.
+    pub fn withdraw_all(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        sender: &Addr,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Treasurer, sender)?;
+        let funds = deps.querier.query_all_balances(&env.contract.address)?;
+        if funds.is_empty() {
+            return Err(ContractError::NoFunds {});
+        }
+        let msgs = self.payout_msgs(deps.storage, &funds)?;
+        let amount = funds
+            .iter()
+            .map(|coin| coin.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(Response::new()
+            .add_messages(msgs)
+            .add_attribute("action", "withdraw_all")
+            .add_attribute("amount", amount))
+    }
 
-    let forward_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: forward_to_address.clone(),
-        token_id: msg.token_id,
-    };
+    pub fn set_payouts(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        payouts: Vec<Payout>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Treasurer, sender)?;
+        if payouts.is_empty() {
+            self.payouts.remove(deps.storage);
+            return Ok(Response::new()
+                .add_attribute("action", "set_payouts")
+                .add_attribute("payouts", "none"));
+        }
 
-    Ok(Response::new()
-        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: env.contract.address.to_string(),
-            msg: to_json_binary(&forward_msg)?,
-            funds: vec![],
+        let total_bps: u32 = payouts.iter().map(|p| u32::from(p.bps)).sum();
+        if total_bps != u32::from(BPS_DENOMINATOR) {
+            return Err(ContractError::InvalidPayouts { total_bps });
+        }
+        let payouts = payouts
+            .into_iter()
+            .map(|p| {
+                Ok(Payout {
+                    address: deps.api.addr_validate(&p.address)?.into_string(),
+                    bps: p.bps,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        self.payouts.save(deps.storage, &payouts)?;
+
+        let res = Response::new().add_attribute("action", "set_payouts");
+        Ok(payouts.into_iter().fold(res, |res, p| {
+            res.add_attribute("payout", format!("{}:{}", p.address, p.bps))
         }))
-        .add_attribute("action", "forward_nft")
-        .add_attribute("recipient", forward_to_address)
-        )
-}
+    }
 
-fn try_receive_cw20(
-    _deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    cw20_msg: Cw20ReceiveMsg,
-) -> Result<Response, ContractError> {
-    let forward_to_wallet = "sei1wpha09pxxmxcu0yvcrcsqew4payuhenpw0c642".to_string();
-    let amount = cw20_msg.amount; // Amount received and parsed from the Cw20ReceiveMsg
-    let forward_msg = Cw20ExecuteMsg::Transfer {
-        recipient: forward_to_wallet,
-        amount,
-    };
+    /// Splits `funds` between the configured payouts, or sends everything to the
+    /// withdraw address when no split is set. Rounding dust goes to the last payout.
+    fn payout_msgs(
+        &self,
+        storage: &dyn Storage,
+        funds: &[Coin],
+    ) -> Result<Vec<BankMsg>, ContractError> {
+        let payouts = match self.payouts.may_load(storage)? {
+            Some(payouts) => payouts,
+            None => match self.withdraw_address.may_load(storage)? {
+                Some(address) => vec![Payout {
+                    address,
+                    bps: BPS_DENOMINATOR,
+                }],
+                None => return Err(ContractError::NoWithdrawAddress {}),
+            },
+        };
 
-    Ok(Response::new()
-        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: info.sender.to_string(), // The address of the CW20 token contract
-            msg: to_json_binary(&forward_msg)?,
-            funds: vec![],
-        }))
-        .add_attribute("action", "forward_cw20_tokens")
-        .add_attribute("amount_forwarded", amount.to_string()))
-   
-}
+        let mut remaining = funds.to_vec();
+        let last = payouts.len() - 1;
+        let mut msgs = vec![];
+        for (i, payout) in payouts.into_iter().enumerate() {
+            let amount: Vec<Coin> = if i == last {
+                remaining.clone()
+            } else {
+                funds
+                    .iter()
+                    .zip(remaining.iter_mut())
+                    .map(|(coin, left)| {
+                        let share = coin.amount.multiply_ratio(payout.bps, BPS_DENOMINATOR);
+                        left.amount -= share;
+                        Coin::new(share.u128(), &coin.denom)
+                    })
+                    .collect()
+            };
+            let amount: Vec<Coin> = amount.into_iter().filter(|c| !c.amount.is_zero()).collect();
+            if !amount.is_empty() {
+                msgs.push(BankMsg::Send {
+                    to_address: payout.address,
+                    amount,
+                });
+            }
+        }
+        Ok(msgs)
+    }
+
+    pub fn try_receive_cw721(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        _info: MessageInfo,
+        msg: Cw721ReceiveMsg,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_not_paused(deps.storage, &env, PauseTarget::Receive)?;
+        self.assert_not_blocked(deps.storage, &[&Addr::unchecked(&msg.sender)])?;
+        let forward_to_address = "sei1wpha09pxxmxcu0yvcrcsqew4payuhenpw0c642".to_string();
+
+        let forward_msg = Cw721ExecuteMsg::TransferNft {
+            recipient: forward_to_address.clone(),
+            token_id: msg.token_id,
+        };
+
+        Ok(Response::new()
+            .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&forward_msg)?,
+                funds: vec![],
+            }))
+            .add_attribute("action", "forward_nft")
+            .add_attribute("recipient", forward_to_address))
+    }
+
+    pub fn try_receive_cw20(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        cw20_msg: Cw20ReceiveMsg,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_not_paused(deps.storage, &env, PauseTarget::Receive)?;
+        self.assert_not_blocked(
+            deps.storage,
+            &[&info.sender, &Addr::unchecked(&cw20_msg.sender)],
+        )?;
+        let forward_to_wallet = "sei1wpha09pxxmxcu0yvcrcsqew4payuhenpw0c642".to_string();
+        let amount = cw20_msg.amount; // Amount received and parsed from the Cw20ReceiveMsg
+        let forward_msg = Cw20ExecuteMsg::Transfer {
+            recipient: forward_to_wallet,
+            amount,
+        };
 
+        Ok(Response::new()
+            .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: info.sender.to_string(), // The address of the CW20 token contract
+                msg: to_json_binary(&forward_msg)?,
+                funds: vec![],
+            }))
+            .add_attribute("action", "forward_cw20_tokens")
+            .add_attribute("amount_forwarded", amount.to_string()))
+    }
 
-//for v2
-fn burn(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    token_id: String,
-) -> Result<Response, ContractError> {
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    //for v2
+    pub fn mint(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_id: String,
+        owner: String,
+        _token_uri: Option<String>,
+        _extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Minter, &info.sender)?;
 
-    // let token = self.tokens.load(deps.storage, &token_id)?;
-    // self.check_can_send(deps.as_ref(), &env, &info, &token)?;
+        Ok(Response::new()
+            .add_attribute("action", "mint")
+            .add_attribute("minter", info.sender)
+            .add_attribute("owner", owner)
+            .add_attribute("token_id", token_id))
+    }
 
-    // self.tokens.remove(deps.storage, &token_id)?;
-    // self.decrement_tokens(deps.storage)?;
+    pub fn set_whitelist(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        target: String,
+        state: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::WhitelistManager, &info.sender)?;
+
+        let target_addr = deps.api.addr_validate(&target)?;
+        // It holds the unsold supply, which would otherwise become NFTs
+        if !state && self.sale_inventory(deps.storage)?.as_ref() == Some(&target_addr) {
+            return Err(ContractError::SaleInventoryWhitelist { address: target });
+        }
+        let mut burned = vec![];
+        if state {
+            let owned_list = self
+                .owned
+                .may_load(deps.storage, target.to_string())?
+                .unwrap_or(vec![]);
+
+            // Whitelisted addresses hold no NFTs, so their locks cannot stand
+            for id in &owned_list {
+                self.locked.remove(deps.storage, id.to_string());
+            }
+            for _ in 0..owned_list.len() {
+                burned.push(self._burn(deps.storage, env.clone(), target_addr.clone())?);
+            }
+        }
 
-    Ok(Response::new()
-        .add_attribute("action", "burn")
-        .add_attribute("sender", info.sender)
-        .add_attribute("token_id", token_id))
-}
+        let was_whitelisted = self
+            .whitelist
+            .may_load(deps.storage, target.to_string())?
+            .unwrap_or(false);
+        let unit = self.get_unit(deps.storage)?;
+        let mut minted = vec![];
+        if was_whitelisted != state {
+            let balance = self
+                .balances
+                .may_load(deps.storage, &target_addr)?
+                .unwrap_or_default();
+            // Otherwise its balance would back no NFT a transfer could burn
+            if !state {
+                for _ in 0..(balance / unit).u128() {
+                    minted.push(self._mint(deps.storage, env.clone(), target_addr.clone())?);
+                }
+            }
+            let exempt = self
+                .exempt_supply
+                .may_load(deps.storage)?
+                .unwrap_or_default();
+            let exempt = if state {
+                exempt + balance
+            } else {
+                exempt.saturating_sub(balance)
+            };
+            self.exempt_supply.save(deps.storage, &exempt)?;
+        }
 
-fn _get_random_number(env: &Env) -> Uint128 {
-    let block_time = env.block.time.seconds(); // Using block time in seconds
-    let block_height = env.block.height; // Using block height
+        self.whitelist
+            .save(deps.storage, target.to_string(), &state)?;
+        let hooks = nft_hook(HookAction::Burn, &target_addr, &burned, unit)
+            .into_iter()
+            .chain(nft_hook(HookAction::Mint, &target_addr, &minted, unit))
+            .collect();
+        Ok(Response::new()
+            .add_submessages(self.hook_messages(deps.storage, hooks)?)
+            .add_attribute("action", "set_whitelist")
+            .add_attribute("target", target)
+            .add_attribute("state", state.to_string())
+            .add_attribute("by", info.sender)
+            .add_events(burn_events(&target_addr, &burned))
+            .add_events(mint_events(&target_addr, &minted)))
+    }
 
-    let seed = block_time as u128 + block_height as u128;
-    Uint128::from(seed % 100) // Adjust the range by changing the modulus
-}
+    pub fn set_base_token_uri(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        id: u8,
+        uri: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::MetadataManager, &info.sender)?;
+        if id == 0 || id > TIER_COUNT {
+            return Err(ContractError::InvalidTier { tier: id });
+        }
+        self.tier_uris.save(deps.storage, id, &uri)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_token_uri")
+            .add_attribute("tier", id.to_string())
+            .add_attribute("uri", uri))
+    }
 
-//for v2
-pub fn mint(
-    deps: DepsMut,
-    info: MessageInfo,
-    token_id: String,
-    owner: String,
-    _token_uri: Option<String>,
-    _extension: Empty,
-) -> Result<Response, ContractError> {
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "mint")
-        .add_attribute("minter", info.sender)
-        .add_attribute("owner", owner)
-        .add_attribute("token_id", token_id))
-}
+    pub fn pause(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        targets: Vec<PauseTarget>,
+        until_height: Option<u64>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Pauser, &info.sender)?;
+        let until = match until_height {
+            Some(height) if height <= env.block.height => {
+                return Err(ContractError::InvalidPauseHeight { height })
+            }
+            Some(height) => Expiration::AtHeight(height),
+            None => Expiration::Never {},
+        };
+        let targets = if targets.is_empty() {
+            PauseTarget::ALL.to_vec()
+        } else {
+            targets
+        };
 
-pub fn update_ownership(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    action: cw_ownable::Action,
-) -> Result<Response, ContractError> {
-    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
-    Ok(Response::new().add_attributes(ownership.into_attributes()))
-}
+        let mut res = Response::new()
+            .add_attribute("action", "pause")
+            .add_attribute("until", until.to_string());
+        for target in targets {
+            self.paused.save(deps.storage, target.as_str(), &until)?;
+            res = res.add_attribute("target", target.as_str());
+        }
+        Ok(res)
+    }
 
+    pub fn unpause(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        targets: Vec<PauseTarget>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Pauser, &info.sender)?;
+        let targets = if targets.is_empty() {
+            PauseTarget::ALL.to_vec()
+        } else {
+            targets
+        };
 
-pub fn set_whitelist(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    target: String,
-    state: bool,
-) -> Result<Response, ContractError> {
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+        let mut res = Response::new().add_attribute("action", "unpause");
+        for target in targets {
+            self.paused.remove(deps.storage, target.as_str());
+            res = res.add_attribute("target", target.as_str());
+        }
+        Ok(res)
+    }
 
-    if state {
-        let owned_list = OWNED
-            .may_load(deps.storage, target.to_string())?
-            .unwrap_or(vec![]);
+    /// A pause past its `until_height` no longer applies, even before anyone
+    /// calls `Unpause`.
+    pub fn is_paused(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        target: PauseTarget,
+    ) -> StdResult<bool> {
+        Ok(self
+            .paused
+            .may_load(storage, target.as_str())?
+            .map_or(false, |until| !until.is_expired(&env.block)))
+    }
 
-        for _ in 0..owned_list.len() {
-            _burn(deps.storage, env.clone(), deps.api.addr_validate(&target)?)?;
+    pub fn assert_not_paused(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        target: PauseTarget,
+    ) -> Result<(), ContractError> {
+        if self.is_paused(storage, env, target)? {
+            return Err(ContractError::Paused {
+                target: target.to_string(),
+            });
         }
+        Ok(())
     }
 
-    WHITELIST.save(deps.storage, target.to_string(), &state)?;
-    Ok(Response::new().add_attribute("action", "set_whitelist"))
-}
+    pub fn set_launch_config(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        config: LaunchConfig,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        self.launch_config.save(deps.storage, &config)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_launch_config")
+            .add_attribute("trading_start", config.trading_start.to_string())
+            .add_attribute("limits_duration", config.limits_duration.to_string()))
+    }
 
-pub fn set_base_token_uri(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    id: u8,
-    uri: String,
-) -> Result<Response, ContractError> {
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
-    if id > 6 || id <= 0 {
-        return Err(ContractError::InvalidInput { });
-    }
-    TOKEN_URI.save(deps.storage,id.to_string(), &uri)?;
-    Ok(Response::new().add_attribute("action", "set_token_uri"))
-}
+    pub fn set_launch_allowlist(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        allowed: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        if allowed {
+            self.launch_allowlist
+                .save(deps.storage, &address, &Empty {})?;
+        } else {
+            self.launch_allowlist.remove(deps.storage, &address);
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_launch_allowlist")
+            .add_attribute("address", address)
+            .add_attribute("allowed", allowed.to_string()))
+    }
 
-fn transfer_from(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    from: String,
-    to: String,
-    amount_or_id: Uint128,
-) -> Result<Response, ContractError> {
-    let from_addr = deps.api.addr_validate(&from)?;
-    let to_addr = deps.api.addr_validate(&to)?;
-
-    let owner_of = OWNER_OF
-        .may_load(deps.storage, amount_or_id.to_string())?
-        .unwrap_or("".to_string());
-    let minted = MINTED.load(deps.storage)?;
-    let is_approved_for_all = APPROVED_FOR_ALL
-        .may_load(deps.storage, (from.to_string(), info.sender.to_string()))?
-        .unwrap_or(false);
-
-    let get_approved = GET_APPROVED
-        .may_load(deps.storage, amount_or_id.to_string())?
-        .unwrap_or("".to_string());
-    let unit = get_unit(deps.storage)?;
-
-    if amount_or_id <= minted {
-        if from != owner_of {
-            return Err(ContractError::InvalidSender {});
+    pub fn set_pool(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        is_pool: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        if is_pool {
+            self.pools.save(deps.storage, &address, &Empty {})?;
+        } else {
+            self.pools.remove(deps.storage, &address);
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_pool")
+            .add_attribute("address", address)
+            .add_attribute("is_pool", is_pool.to_string()))
+    }
+
+    pub fn set_fee_config(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        config: Option<FeeConfig>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        let config = match config {
+            Some(config) => config,
+            None => {
+                self.fee_config.remove(deps.storage);
+                return Ok(Response::new()
+                    .add_attribute("action", "set_fee_config")
+                    .add_attribute("fee", "none"));
+            }
+        };
+
+        for bps in [config.transfer_bps, config.buy_bps, config.sell_bps] {
+            if bps > BPS_DENOMINATOR {
+                return Err(ContractError::InvalidFee { bps });
+            }
         }
+        let recipient = deps.api.addr_validate(&config.recipient)?;
+        self.assert_not_blocked(deps.storage, &[&recipient])?;
+        let config = FeeConfig {
+            recipient: recipient.into_string(),
+            ..config
+        };
+        self.fee_config.save(deps.storage, &config)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_fee_config")
+            .add_attribute("recipient", config.recipient)
+            .add_attribute("transfer_bps", config.transfer_bps.to_string())
+            .add_attribute("buy_bps", config.buy_bps.to_string())
+            .add_attribute("sell_bps", config.sell_bps.to_string()))
+    }
 
-        if to == "" {
-            return Err(ContractError::InvalidRecipient {});
+    pub fn set_fee_exempt(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        exempt: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        if exempt {
+            self.fee_exempt.save(deps.storage, &address, &Empty {})?;
+        } else {
+            self.fee_exempt.remove(deps.storage, &address);
         }
+        Ok(Response::new()
+            .add_attribute("action", "set_fee_exempt")
+            .add_attribute("address", address)
+            .add_attribute("exempt", exempt.to_string()))
+    }
 
-        if info.sender.to_string() != from
+    /// Fee owed on moving `amount` from `from` to `to`, with the address it is
+    /// paid to. `None` when no fee applies. Sales out of the sale inventory are
+    /// priced per whole token, so the inventory never pays or charges fees.
+    pub fn transfer_fee(
+        &self,
+        storage: &dyn Storage,
+        from: &Addr,
+        to: &Addr,
+        amount: Uint128,
+    ) -> StdResult<Option<(Addr, Uint128)>> {
+        let config = match self.fee_config.may_load(storage)? {
+            Some(config) => config,
+            None => return Ok(None),
+        };
+        let recipient = Addr::unchecked(config.recipient);
+        let inventory = self.sale_inventory(storage)?;
+        if from == to
+            || from == recipient
+            || to == recipient
+            || inventory.as_ref() == Some(from)
+            || inventory.as_ref() == Some(to)
+            || self.fee_exempt.has(storage, from)
+            || self.fee_exempt.has(storage, to)
+        {
+            return Ok(None);
+        }
+
+        let bps = if self.pools.has(storage, from) {
+            config.buy_bps
+        } else if self.pools.has(storage, to) {
+            config.sell_bps
+        } else {
+            config.transfer_bps
+        };
+        let fee = amount.multiply_ratio(bps, BPS_DENOMINATOR);
+        if fee.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some((recipient, fee)))
+    }
+
+    pub fn set_blocklist(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        blocked: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        if blocked {
+            self.blocklist.save(deps.storage, &address, &Empty {})?;
+        } else {
+            self.blocklist.remove(deps.storage, &address);
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_blocklist")
+            .add_event(
+                Event::new("blocklist_update")
+                    .add_attribute("address", address)
+                    .add_attribute("blocked", blocked.to_string())
+                    .add_attribute("by", info.sender),
+            ))
+    }
+
+    pub fn add_hook(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        policy: HookFailurePolicy,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        if !self.hooks.has(deps.storage, &address)
+            && self
+                .hooks
+                .keys(deps.storage, None, None, Order::Ascending)
+                .count()
+                >= MAX_HOOKS
+        {
+            return Err(ContractError::TooManyHooks { max: MAX_HOOKS });
+        }
+        self.hooks.save(deps.storage, &address, &policy)?;
+        Ok(Response::new()
+            .add_attribute("action", "add_hook")
+            .add_attribute("address", address)
+            .add_attribute(
+                "policy",
+                match policy {
+                    HookFailurePolicy::Ignore => "ignore",
+                    HookFailurePolicy::Revert => "revert",
+                },
+            ))
+    }
+
+    pub fn remove_hook(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        self.hooks.remove(deps.storage, &address);
+        Ok(Response::new()
+            .add_attribute("action", "remove_hook")
+            .add_attribute("address", address))
+    }
+
+    /// One submessage per registered hook and message. Failures revert the
+    /// transaction or come back to [`Self::reply`], depending on the hook.
+    fn hook_messages(
+        &self,
+        storage: &dyn Storage,
+        msgs: Vec<Cw404HookMsg>,
+    ) -> StdResult<Vec<SubMsg<C>>> {
+        if msgs.is_empty() {
+            return Ok(vec![]);
+        }
+        let hooks = self
+            .hooks
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut submsgs = vec![];
+        for msg in msgs {
+            for (hook, policy) in &hooks {
+                let wasm = msg.clone().into_cosmos_msg(hook)?;
+                submsgs.push(match policy {
+                    HookFailurePolicy::Ignore => SubMsg::reply_on_error(wasm, HOOK_REPLY_ID),
+                    HookFailurePolicy::Revert => SubMsg::new(wasm),
+                });
+            }
+        }
+        Ok(submsgs)
+    }
+
+    pub fn assert_not_blocked(
+        &self,
+        storage: &dyn Storage,
+        addresses: &[&Addr],
+    ) -> Result<(), ContractError> {
+        for address in addresses {
+            if self.blocklist.has(storage, address) {
+                return Err(ContractError::Blocked {
+                    address: address.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Account that `NativeMint` sales are paid out from.
+    pub fn sale_inventory(&self, storage: &dyn Storage) -> StdResult<Option<Addr>> {
+        self.sale_inventory.may_load(storage)
+    }
+
+    /// Applies the trading gate and the launch window limits to a transfer of
+    /// `amount` from `from` to `to`, of which the recipient, currently holding
+    /// `receiver_balance`, keeps `received` after fees.
+    ///
+    /// The max-transaction limit is skipped for sales out of the sale inventory
+    /// and for transfers into a pool; the max-wallet limit is skipped when the
+    /// recipient is the sale inventory or a pool.
+    #[allow(clippy::too_many_arguments)]
+    fn assert_launch_limits(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        from: &Addr,
+        to: &Addr,
+        amount: Uint128,
+        received: Uint128,
+        receiver_balance: Uint128,
+    ) -> Result<(), ContractError> {
+        let config = match self.launch_config.may_load(storage)? {
+            Some(config) => config,
+            None => return Ok(()),
+        };
+        let inventory = self.sale_inventory(storage)?;
+        let from_inventory = inventory.as_ref() == Some(from);
+        let to_inventory = inventory.as_ref() == Some(to);
+
+        let now = env.block.time;
+        if now < config.trading_start {
+            if from_inventory || self.launch_allowlist.has(storage, from) {
+                return Ok(());
+            }
+            return Err(ContractError::TradingNotStarted {});
+        }
+        if now >= config.trading_start.plus_seconds(config.limits_duration) {
+            return Ok(());
+        }
+
+        let to_pool = self.pools.has(storage, to);
+        if let Some(max) = config.max_tx_amount {
+            if amount > max && !from_inventory && !to_pool {
+                return Err(ContractError::MaxTxExceeded { amount, max });
+            }
+        }
+        if let Some(max) = config.max_wallet_amount {
+            let balance = receiver_balance + received;
+            if balance > max && !to_inventory && !to_pool {
+                return Err(ContractError::MaxWalletExceeded {
+                    address: to.to_string(),
+                    balance,
+                    max,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn grant_role(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        self.roles
+            .save(deps.storage, (role.as_str(), &address), &Empty {})?;
+        Ok(Response::new()
+            .add_attribute("action", "grant_role")
+            .add_attribute("role", role.as_str())
+            .add_attribute("address", address))
+    }
+
+    /// Revoking the only remaining admin is refused, as nobody could grant
+    /// roles afterwards.
+    pub fn revoke_role(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Admin, &info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        self.assert_role(deps.storage, role, &address)?;
+        if role == Role::Admin {
+            let admins = self
+                .roles
+                .prefix(Role::Admin.as_str())
+                .keys(deps.storage, None, None, Order::Ascending)
+                .take(2)
+                .count();
+            if admins < 2 {
+                return Err(ContractError::LastAdmin {});
+            }
+        }
+        self.roles.remove(deps.storage, (role.as_str(), &address));
+        Ok(Response::new()
+            .add_attribute("action", "revoke_role")
+            .add_attribute("role", role.as_str())
+            .add_attribute("address", address))
+    }
+
+    pub fn has_role(&self, storage: &dyn Storage, role: Role, address: &Addr) -> bool {
+        self.roles.has(storage, (role.as_str(), address))
+    }
+
+    pub fn assert_role(
+        &self,
+        storage: &dyn Storage,
+        role: Role,
+        address: &Addr,
+    ) -> Result<(), ContractError> {
+        if !self.has_role(storage, role, address) {
+            return Err(ContractError::MissingRole {
+                role: role.to_string(),
+                address: address.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Sets the extension given to NFTs minted in `tier` from now on. Existing
+    /// tokens keep their metadata and are changed through `UpdateNftMetadata`.
+    pub fn set_tier_metadata(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        tier: u8,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_metadata_editable(deps.storage, &info.sender)?;
+        if tier == 0 || tier > TIER_COUNT {
+            return Err(ContractError::InvalidTier { tier });
+        }
+        self.tier_extensions.save(deps.storage, tier, &extension)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_tier_metadata")
+            .add_attribute("tier", tier.to_string()))
+    }
+
+    pub fn update_nft_metadata(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_id: String,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_metadata_editable(deps.storage, &info.sender)?;
+        let id = self.parse_token_id(deps.storage, &token_id)?;
+        let mut token = self.tokens.load(deps.storage, &id.to_string())?;
+        token.extension = extension;
+        self.tokens.save(deps.storage, &id.to_string(), &token)?;
+        Ok(Response::new()
+            .add_attribute("action", "update_nft_metadata")
+            .add_attribute("token_id", id))
+    }
+
+    /// Permanently locks tier and token metadata.
+    pub fn freeze_metadata(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_metadata_editable(deps.storage, &info.sender)?;
+        self.metadata_frozen.save(deps.storage, &true)?;
+        Ok(Response::new().add_attribute("action", "freeze_metadata"))
+    }
+
+    /// `NAME` and `SYMBOL` back `TokenInfo` and are kept in step with `CONTRACT_INFO`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_contract_info(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        name: Option<String>,
+        symbol: Option<String>,
+        description: Option<String>,
+        image: Option<String>,
+        external_link: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        let mut contract_info = self.assert_contract_info_editable(deps.storage, &info.sender)?;
+        if let Some(name) = name {
+            validate_name(&name)?;
+            self.name.save(deps.storage, &name)?;
+            contract_info.name = name;
+        }
+        if let Some(symbol) = symbol {
+            validate_symbol(&symbol)?;
+            self.symbol.save(deps.storage, &symbol)?;
+            contract_info.symbol = symbol;
+        }
+        let non_empty = |value: String| Some(value).filter(|value| !value.is_empty());
+        if let Some(description) = description {
+            contract_info.description = non_empty(description);
+        }
+        if let Some(image) = image {
+            contract_info.image = non_empty(image);
+        }
+        if let Some(external_link) = external_link {
+            contract_info.external_link = non_empty(external_link);
+        }
+        self.contract_info.save(deps.storage, &contract_info)?;
+        Ok(Response::new()
+            .add_attribute("action", "update_contract_info")
+            .add_attribute("name", contract_info.name)
+            .add_attribute("symbol", contract_info.symbol))
+    }
+
+    pub fn freeze_contract_info(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        let mut contract_info = self.assert_contract_info_editable(deps.storage, &info.sender)?;
+        contract_info.frozen = true;
+        self.contract_info.save(deps.storage, &contract_info)?;
+        Ok(Response::new().add_attribute("action", "freeze_contract_info"))
+    }
+
+    /// Admins may edit the contract info until it has been frozen.
+    fn assert_contract_info_editable(
+        &self,
+        storage: &dyn Storage,
+        sender: &Addr,
+    ) -> Result<ContractInfoResponse, ContractError> {
+        self.assert_role(storage, Role::Admin, sender)?;
+        let contract_info = self.contract_info.load(storage)?;
+        if contract_info.frozen {
+            return Err(ContractError::ContractInfoFrozen {});
+        }
+        Ok(contract_info)
+    }
+
+    pub fn update_marketing(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        project: Option<String>,
+        description: Option<String>,
+        marketing: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Marketing, &info.sender)?;
+        let mut marketing_info = self
+            .marketing_info
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        let non_empty = |value: String| Some(value).filter(|value| !value.is_empty());
+        if let Some(project) = project {
+            marketing_info.project = non_empty(project);
+        }
+        if let Some(description) = description {
+            marketing_info.description = non_empty(description);
+        }
+        self.marketing_info.save(deps.storage, &marketing_info)?;
+
+        let mut response = Response::new().add_attribute("action", "update_marketing");
+        if let Some(marketing) = marketing {
+            let marketing = non_empty(marketing)
+                .map(|address| deps.api.addr_validate(&address))
+                .transpose()?;
+            self.roles
+                .remove(deps.storage, (Role::Marketing.as_str(), &info.sender));
+            if let Some(marketing) = &marketing {
+                self.roles.save(
+                    deps.storage,
+                    (Role::Marketing.as_str(), marketing),
+                    &Empty {},
+                )?;
+            }
+            // Attribute values may not be empty
+            response = response.add_attribute(
+                "marketing",
+                marketing.map_or_else(|| "none".to_string(), Addr::into_string),
+            );
+        }
+        Ok(response)
+    }
+
+    pub fn upload_logo(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        logo: Logo,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_role(deps.storage, Role::Marketing, &info.sender)?;
+        verify_logo(&logo)?;
+        self.logo.save(deps.storage, &logo)?;
+
+        let mut marketing_info = self
+            .marketing_info
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        marketing_info.logo = Some(match logo {
+            Logo::Url(url) => LogoInfo::Url(url),
+            Logo::Embedded(_) => LogoInfo::Embedded,
+        });
+        self.marketing_info.save(deps.storage, &marketing_info)?;
+        Ok(Response::new().add_attribute("action", "upload_logo"))
+    }
+
+    /// Metadata managers may edit metadata until it has been frozen.
+    fn assert_metadata_editable(
+        &self,
+        storage: &dyn Storage,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        if self.metadata_frozen.may_load(storage)?.unwrap_or(false) {
+            return Err(ContractError::MetadataFrozen {});
+        }
+        self.assert_role(storage, Role::MetadataManager, sender)
+    }
+
+    /// ERC404 `transferFrom`: values up to `MINTED` are treated as token ids, anything
+    /// larger as a fungible amount drawn from the caller's allowance.
+    pub fn transfer_amount_or_id(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        from: String,
+        to: String,
+        amount_or_id: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        let minted = self.minted.load(deps.storage)?;
+
+        if amount_or_id <= minted {
+            self._transfer_nft(deps, env, info, from, to, amount_or_id)
+        } else {
+            self.assert_not_paused(deps.storage, &env, PauseTarget::FungibleTransfers)?;
+            let allowed = self
+                .allowance
+                .may_load(deps.storage, (from.clone(), info.sender.to_string()))?
+                .unwrap_or(Uint128::zero());
+            if allowed != Uint128::MAX {
+                let remaining = allowed.checked_sub(amount_or_id).map_err(|_| {
+                    ContractError::InsufficientAllowance {
+                        owner: from.clone(),
+                        spender: info.sender.to_string(),
+                        allowance: allowed,
+                        amount: amount_or_id,
+                    }
+                })?;
+                self.allowance.save(
+                    deps.storage,
+                    (from.clone(), info.sender.to_string()),
+                    &remaining,
+                )?;
+            }
+            let response = self._transfer(
+                deps,
+                env,
+                info.clone(),
+                from,
+                to,
+                amount_or_id,
+                "transfer_from".to_string(),
+            )?;
+            Ok(response.add_attribute("by", info.sender))
+        }
+    }
+
+    /// Moves a single NFT together with one unit of the fungible balance backing it.
+    /// No transfer fee is taken: the recipient must get the whole unit backing
+    /// the NFT.
+    pub fn _transfer_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        from: String,
+        to: String,
+        token_id: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_not_paused(deps.storage, &env, PauseTarget::NftTransfers)?;
+        let from_addr = deps.api.addr_validate(&from)?;
+        let to_addr = deps.api.addr_validate(&to)?;
+        self.assert_not_blocked(deps.storage, &[&info.sender, &from_addr, &to_addr])?;
+
+        let owner_of = self
+            .owner_of
+            .may_load(deps.storage, token_id.to_string())?
+            .unwrap_or("".to_string());
+        let is_approved_for_all = self
+            .approved_for_all
+            .may_load(deps.storage, (from.to_string(), info.sender.to_string()))?
+            .unwrap_or(false);
+        let unit = self.get_unit(deps.storage)?;
+
+        if from != owner_of {
+            return Err(ContractError::NotTokenOwner {
+                token_id: token_id.to_string(),
+                address: from,
+            });
+        }
+
+        let mut token_info = self.tokens.load(deps.storage, &token_id.to_string())?;
+        if info.sender != from
             && !is_approved_for_all
-            && info.sender.to_string() != get_approved
+            && !self.is_approved(deps.storage, &env, &token_info, &info.sender, token_id)?
         {
-            return Err(ContractError::Unauthorized {});
+            return Err(ContractError::Unauthorized {
+                address: info.sender.into_string(),
+                token_id: token_id.to_string(),
+            });
         }
 
-        // Prevents exploiting two different states of transferFrom can lead to a bug that allows minting 
+        // Prevents exploiting two different states of transferFrom can lead to a bug that allows minting
         // CW-721 tokens out of thin air through a whitelist
-        if WHITELIST
+        if self
+            .whitelist
             .may_load(deps.storage, to.clone())?
             .unwrap_or_default()
         {
-            return Err(ContractError::InvalidRecipient {});
+            return Err(ContractError::WhitelistedRecipient { address: to });
         }
 
-        BALANCES.update(
+        let balance_before_receiver = self
+            .balances
+            .may_load(deps.storage, &to_addr)?
+            .unwrap_or_default();
+        self.assert_launch_limits(
             deps.storage,
+            &env,
             &from_addr,
-            |balance: Option<Uint128>| -> StdResult<_> {
-                Ok(balance.unwrap_or_default().checked_sub(unit)?)
-            },
+            &to_addr,
+            unit,
+            unit,
+            balance_before_receiver,
         )?;
-        BALANCES.update(
+
+        let balance_before_sender = self
+            .balances
+            .may_load(deps.storage, &from_addr)?
+            .unwrap_or_default();
+        if balance_before_sender < unit {
+            return Err(ContractError::InsufficientBalance {
+                address: from,
+                balance: balance_before_sender,
+                amount: unit,
+            });
+        }
+        self._set_balance(
+            deps.storage,
+            env.block.height,
+            &from_addr,
+            balance_before_sender - unit,
+        )?;
+        // Read again, the recipient may be the sender
+        let balance_receiver = self
+            .balances
+            .may_load(deps.storage, &to_addr)?
+            .unwrap_or_default();
+        self._set_balance(
             deps.storage,
+            env.block.height,
             &to_addr,
-            |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + unit) },
+            balance_receiver + unit,
         )?;
 
-        OWNER_OF.save(deps.storage, amount_or_id.to_string(), &to)?;
-        let mut token_info = TOKENS.load(deps.storage, &amount_or_id.to_string())?;
-    
-        token_info.owner = deps.api.addr_validate(&to)?;
-        
-        TOKENS.save(deps.storage, &amount_or_id.to_string(), &token_info)?;
+        self.owner_of
+            .save(deps.storage, token_id.to_string(), &to)?;
+        token_info.owner = to_addr;
+        token_info.approvals = vec![];
+        self.tokens
+            .save(deps.storage, &token_id.to_string(), &token_info)?;
+
+        self.get_approved.remove(deps.storage, token_id.to_string());
+        self.locked.remove(deps.storage, token_id.to_string());
+        self._remove_owned(deps.storage, env.block.height, &from, token_id)?;
+        self._push_owned(deps.storage, env.block.height, &to, token_id)?;
+        let hook = Cw404HookMsg {
+            action: HookAction::NftTransfer,
+            from: Some(from.clone()),
+            to: Some(to.clone()),
+            amount: unit,
+            token_ids: vec![token_id.to_string()],
+        };
+        Ok(Response::new()
+            .add_submessages(self.hook_messages(deps.storage, vec![hook])?)
+            .add_attribute("action", "transfer")
+            .add_attribute("from", from.clone())
+            .add_attribute("to", to.clone())
+            .add_attribute("amount", token_id)
+            .add_event(
+                Event::new("cw721_transfer")
+                    .add_attribute("from", from)
+                    .add_attribute("to", to)
+                    .add_attribute("token_id", token_id)
+                    .add_attribute("by", info.sender),
+            ))
+    }
 
-        GET_APPROVED.remove(deps.storage, amount_or_id.to_string());
-        let mut vec_updated_id = OWNED
-            .may_load(deps.storage, from.clone())?
-            .unwrap_or(vec![]);
+    /// Moves each listed NFT intact, then the part of `amount` they do not
+    /// account for as a plain fungible transfer.
+    pub fn transfer_with_ids(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        amount: Uint128,
+        token_ids: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        let unit = self.get_unit(deps.storage)?;
+        let required = unit
+            .checked_mul(Uint128::from(token_ids.len() as u128))
+            .map_err(StdError::from)?;
+        if amount < required {
+            return Err(ContractError::AmountBelowTokenIds { amount, required });
+        }
+        // The max-transaction limit caps the whole call. Each NFT and the
+        // remainder are checked again as they move, which keeps the wallet
+        // limit on the recipient's running balance.
+        let to_addr = deps.api.addr_validate(&recipient)?;
+        let receiver_balance = self
+            .balances
+            .may_load(deps.storage, &to_addr)?
+            .unwrap_or_default();
+        self.assert_launch_limits(
+            deps.storage,
+            &env,
+            &info.sender,
+            &to_addr,
+            amount,
+            Uint128::zero(),
+            receiver_balance,
+        )?;
 
-        let updated_id = vec_updated_id.get(vec_updated_id.len() - 1).unwrap();
-        let owned_index = OWNED_INDEX
-            .may_load(deps.storage, amount_or_id.to_string())?
-            .unwrap_or(Uint128::zero());
+        let mut response = Response::new()
+            .add_attribute("action", "transfer_with_ids")
+            .add_attribute("from", info.sender.as_str())
+            .add_attribute("to", recipient.as_str())
+            .add_attribute("amount", amount)
+            .add_attribute("token_ids", token_ids.join(","));
+        for token_id in token_ids {
+            let id = self.parse_token_id(deps.storage, &token_id)?;
+            let res = self._transfer_nft(
+                deps.branch(),
+                env.clone(),
+                info.clone(),
+                info.sender.to_string(),
+                recipient.clone(),
+                id,
+            )?;
+            response = response
+                .add_submessages(res.messages)
+                .add_events(res.events);
+        }
 
-        vec_updated_id[owned_index.u128() as usize] = updated_id.clone();
-        vec_updated_id.pop();
-        OWNED.save(deps.storage, from.clone(), &vec_updated_id)?;
+        let remainder = amount - required;
+        if !remainder.is_zero() {
+            self.assert_not_paused(deps.storage, &env, PauseTarget::FungibleTransfers)?;
+            let res = self._transfer(
+                deps,
+                env,
+                info.clone(),
+                info.sender.to_string(),
+                recipient,
+                remainder,
+                "transfer_with_ids".to_string(),
+            )?;
+            let fee_attributes = res
+                .attributes
+                .into_iter()
+                .filter(|attr| attr.key == "fee" || attr.key == "fee_recipient");
+            response = response
+                .add_attributes(fee_attributes)
+                .add_submessages(res.messages)
+                .add_events(res.events);
+        }
+        Ok(response)
+    }
 
-        let mut to_owned = OWNED.may_load(deps.storage, to.clone())?.unwrap_or(vec![]);
-        to_owned.push(amount_or_id);
-        OWNED.save(deps.storage, to.clone(), &to_owned)?;
+    /// Replaces the sender's burn order. Every id must belong to the sender; an
+    /// empty list restores the default newest-first order.
+    pub fn set_burn_order(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        let owner = info.sender.to_string();
+        if token_ids.is_empty() {
+            self.burn_order.remove(deps.storage, owner);
+            return Ok(Response::new().add_attribute("action", "set_burn_order"));
+        }
 
-        // _ownedIndex[amountOrId] = _owned[to].length - 1;
-        OWNED_INDEX.save(
-            deps.storage,
-            amount_or_id.to_string(),
-            &Uint128::from((to_owned.len() - 1) as u128),
-        )?;
+        let ids = token_ids
+            .iter()
+            .map(|token_id| {
+                let id = self.parse_token_id(deps.storage, token_id)?;
+                if self.owner_of.load(deps.storage, id.to_string())? != owner {
+                    return Err(ContractError::Unauthorized {
+                        address: owner.clone(),
+                        token_id: id.to_string(),
+                    });
+                }
+                Ok(id)
+            })
+            .collect::<Result<Vec<_>, ContractError>>()?;
+        self.burn_order.save(deps.storage, owner, &ids)?;
         Ok(Response::new()
-            .add_attribute("action", "transfer")
-            .add_attribute("from", from)
-            .add_attribute("to", to)
-            .add_attribute("amount", amount_or_id))
-    } else {
-        let allowed = ALLOWANCE
-            .may_load(deps.storage, (from.clone(), info.sender.to_string()))?
-            .unwrap_or(Uint128::zero());
-        if allowed != Uint128::MAX {
-            ALLOWANCE.update(
-                deps.storage,
-                (from.clone(), info.sender.to_string()),
-                |allow: Option<Uint128>| -> StdResult<_> {
-                    Ok(allow.unwrap_or_default().checked_sub(amount_or_id)?)
-                },
-            )?;
+            .add_attribute("action", "set_burn_order")
+            .add_attribute("token_ids", token_ids.join(",")))
+    }
+
+    /// Locked NFTs are skipped by `_burn` but can still be moved explicitly;
+    /// a move clears the lock.
+    pub fn set_locked(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_id: String,
+        locked: bool,
+    ) -> Result<Response<C>, ContractError> {
+        let id = self.parse_token_id(deps.storage, &token_id)?;
+        if self.owner_of.load(deps.storage, id.to_string())? != info.sender.as_str() {
+            return Err(ContractError::Unauthorized {
+                address: info.sender.into_string(),
+                token_id: id.to_string(),
+            });
         }
-        let response = _transfer(
-            deps,
-            env,
-            info.clone(),
-            from,
-            to,
-            amount_or_id,
-            "transfer_from".to_string(),
+        if locked {
+            self.locked.save(deps.storage, id.to_string(), &true)?;
+        } else {
+            self.locked.remove(deps.storage, id.to_string());
+        }
+        Ok(Response::new()
+            .add_attribute("action", if locked { "lock" } else { "unlock" })
+            .add_attribute("owner", info.sender)
+            .add_attribute("token_id", id))
+    }
+
+    fn token_locked(&self, storage: &dyn Storage, token_id: Uint128) -> StdResult<bool> {
+        Ok(self
+            .locked
+            .may_load(storage, token_id.to_string())?
+            .unwrap_or(false))
+    }
+
+    /// The NFTs that `count` burns take from `from`, in order: the first unlocked
+    /// ids of its burn order, then its newest unlocked ids. The owned list is
+    /// reordered the way `_remove_owned` does between burns.
+    fn burn_candidates(
+        &self,
+        storage: &dyn Storage,
+        from: &Addr,
+        count: u128,
+    ) -> Result<Vec<Uint128>, ContractError> {
+        let mut owned = self
+            .owned
+            .may_load(storage, from.to_string())?
+            .unwrap_or_default();
+        let mut order = vec![];
+        for id in self
+            .burn_order
+            .may_load(storage, from.to_string())?
+            .unwrap_or_default()
+        {
+            if owned.contains(&id) && !self.token_locked(storage, id)? {
+                order.push(id);
+            }
+        }
+
+        let mut ids = vec![];
+        for _ in 0..count {
+            let id = if order.is_empty() {
+                let mut newest = None;
+                for id in owned.iter().rev() {
+                    if !self.token_locked(storage, *id)? {
+                        newest = Some(*id);
+                        break;
+                    }
+                }
+                // Locked tokens are never picked, so nothing is burned when none is left
+                newest.ok_or_else(|| ContractError::PreventBurn {
+                    owner: from.to_string(),
+                })?
+            } else {
+                order.remove(0)
+            };
+            if let Some(index) = owned.iter().position(|owned_id| *owned_id == id) {
+                owned.swap_remove(index);
+            }
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+
+    /// Removes `token_id` from the owned list of `owner`, moving the last id into
+    /// its slot and keeping `owned_index` in step.
+    fn _remove_owned(
+        &self,
+        storage: &mut dyn Storage,
+        height: u64,
+        owner: &str,
+        token_id: Uint128,
+    ) -> Result<(), ContractError> {
+        let mut owned = self
+            .owned
+            .may_load(storage, owner.to_string())?
+            .unwrap_or_default();
+        let index = self
+            .owned_index
+            .may_load(storage, token_id.to_string())?
+            .unwrap_or_default()
+            .u128() as usize;
+        if owned.get(index) != Some(&token_id) {
+            return Err(ContractError::TokenNotFound {
+                token_id: token_id.to_string(),
+            });
+        }
+
+        owned.swap_remove(index);
+        if let Some(moved) = owned.get(index) {
+            self.owned_index
+                .save(storage, moved.to_string(), &Uint128::from(index as u128))?;
+        }
+        let owner_addr = Addr::unchecked(owner);
+        if owned.is_empty() {
+            self.owned.remove(storage, owner.to_string());
+            self.nft_counts.remove(storage, &owner_addr, height)?;
+            adjust_count(storage, &self.nft_holder_count, false)?;
+        } else {
+            self.owned.save(storage, owner.to_string(), &owned)?;
+            self.nft_counts
+                .save(storage, &owner_addr, &(owned.len() as u32), height)?;
+        }
+        self.owned_index.remove(storage, token_id.to_string());
+        Ok(())
+    }
+
+    /// Appends `token_id` to the owned list of `owner` and records its index.
+    fn _push_owned(
+        &self,
+        storage: &mut dyn Storage,
+        height: u64,
+        owner: &str,
+        token_id: Uint128,
+    ) -> StdResult<()> {
+        let mut owned = self
+            .owned
+            .may_load(storage, owner.to_string())?
+            .unwrap_or_default();
+        if owned.is_empty() {
+            adjust_count(storage, &self.nft_holder_count, true)?;
+        }
+        owned.push(token_id);
+        self.owned.save(storage, owner.to_string(), &owned)?;
+        self.nft_counts.save(
+            storage,
+            &Addr::unchecked(owner),
+            &(owned.len() as u32),
+            height,
+        )?;
+
+        // _ownedIndex[amountOrId] = _owned[to].length - 1;
+        self.owned_index.save(
+            storage,
+            token_id.to_string(),
+            &Uint128::from((owned.len() - 1) as u128),
         )
-        .unwrap();
-        Ok(response.add_attribute("by", info.sender))
     }
-}
 
-fn approve(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    spender: String,
-    amount_or_id: Uint128,
-) -> Result<Response, ContractError> {
-    // self._update_approvals(deps, &env, &info, &spender, &token_id, true, expires)?;
-    let minted = MINTED.load(deps.storage)?;
-
-    if amount_or_id <= minted && amount_or_id > Uint128::zero() {
-        let owner = OWNER_OF
-            .may_load(deps.storage, amount_or_id.to_string())?
+    /// The only writer of `balances`. Zero balances are removed, so accounts
+    /// list current holders only, and `holder_count` follows along. `height`
+    /// is the current block, recorded in the balance history.
+    fn _set_balance(
+        &self,
+        storage: &mut dyn Storage,
+        height: u64,
+        address: &Addr,
+        balance: Uint128,
+    ) -> StdResult<()> {
+        let before = self
+            .balances
+            .may_load(storage, address)?
+            .unwrap_or_default();
+        if before.is_zero() != balance.is_zero() {
+            adjust_count(storage, &self.holder_count, before.is_zero())?;
+        }
+        if self
+            .whitelist
+            .may_load(storage, address.to_string())?
+            .unwrap_or(false)
+        {
+            let exempt = self.exempt_supply.may_load(storage)?.unwrap_or_default();
+            self.exempt_supply
+                .save(storage, &(exempt + balance).saturating_sub(before))?;
+        }
+        if balance.is_zero() {
+            self.balances.remove(storage, address, height)
+        } else {
+            self.balances.save(storage, address, &balance, height)
+        }
+    }
+
+    /// Parses a cw721 string token id and checks that the NFT currently exists.
+    pub fn parse_token_id(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> Result<Uint128, ContractError> {
+        let id = Uint128::from_str(token_id).map_err(|_| ContractError::InvalidTokenId {
+            token_id: token_id.to_string(),
+        })?;
+        if id.is_zero() {
+            return Err(ContractError::InvalidTokenId {
+                token_id: token_id.to_string(),
+            });
+        }
+        if !self.owner_of.has(storage, id.to_string()) {
+            return Err(ContractError::TokenNotFound {
+                token_id: token_id.to_string(),
+            });
+        }
+        Ok(id)
+    }
+
+    /// Checks the single-token approval, honouring the expiration recorded by `Approve`.
+    fn is_approved(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        token_info: &TokenInfo<T>,
+        spender: &Addr,
+        token_id: Uint128,
+    ) -> Result<bool, ContractError> {
+        let get_approved = self
+            .get_approved
+            .may_load(storage, token_id.to_string())?
+            .unwrap_or_default();
+        if *spender != get_approved {
+            return Ok(false);
+        }
+        let expired = token_info
+            .approvals
+            .iter()
+            .any(|apr| apr.spender == *spender && apr.is_expired(&env.block));
+        Ok(!expired)
+    }
+
+    pub fn approve_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: Uint128,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        let owner = self
+            .owner_of
+            .may_load(deps.storage, token_id.to_string())?
             .unwrap_or("".to_string());
+        self.assert_not_blocked(
+            deps.storage,
+            &[&info.sender, &spender_addr, &Addr::unchecked(&owner)],
+        )?;
 
-        let is_approved_for_all = APPROVED_FOR_ALL
+        let is_approved_for_all = self
+            .approved_for_all
             .may_load(deps.storage, (owner.to_string(), info.sender.to_string()))?
             .unwrap_or(false);
-        if info.sender.to_string() != owner.to_string() && !is_approved_for_all {
-            return Err(ContractError::Unauthorized {});
+        if info.sender != owner && !is_approved_for_all {
+            return Err(ContractError::Unauthorized {
+                address: info.sender.into_string(),
+                token_id: token_id.to_string(),
+            });
+        }
+
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
         }
 
-        GET_APPROVED.save(deps.storage, amount_or_id.to_string(), &spender)?;
+        let mut token_info = self.tokens.load(deps.storage, &token_id.to_string())?;
+        token_info.approvals = vec![Approval {
+            spender: spender_addr,
+            expires,
+        }];
+        self.tokens
+            .save(deps.storage, &token_id.to_string(), &token_info)?;
+
+        self.get_approved
+            .save(deps.storage, token_id.to_string(), &spender)?;
         Ok(Response::new()
             .add_attribute("action", "approve")
-            .add_attribute("sender", owner.to_string())
+            .add_attribute("sender", owner)
             .add_attribute("spender", spender)
-            .add_attribute("token_id", amount_or_id))
-    } else {
-        // ALLOWANCE
-        ALLOWANCE.save(
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn increase_allowance(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        spender: String,
+        amount: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        self.assert_not_blocked(deps.storage, &[&info.sender, &spender_addr])?;
+        self.allowance.save(
             deps.storage,
             (info.sender.to_string(), spender.clone()),
-            &amount_or_id,
+            &amount,
         )?;
 
         Ok(Response::new()
             .add_attribute("action", "approve")
             .add_attribute("sender", info.sender)
             .add_attribute("spender", spender)
-            .add_attribute("token_id", amount_or_id))
+            .add_attribute("amount", amount))
+    }
+
+    pub fn transfer(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        from: String,
+        to: String,
+        amount: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_not_paused(deps.storage, &env, PauseTarget::FungibleTransfers)?;
+        self._transfer(deps, env, info, from, to, amount, "transfer".to_string())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn send(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        from: String,
+        contract: String,
+        msg: Binary,
+        amount: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        self.assert_not_paused(deps.storage, &env, PauseTarget::FungibleTransfers)?;
+        let from_addr = deps.api.addr_validate(&from)?;
+        let contract_addr = deps.api.addr_validate(&contract)?;
+        let plan = self.plan_transfer(
+            deps.storage,
+            &env,
+            &info.sender,
+            &from_addr,
+            &contract_addr,
+            amount,
+        )?;
+        // The receiving contract is told what reached it after the fee
+        let received = plan.received;
+        let response = self._apply_transfer(
+            deps.storage,
+            &env,
+            &from_addr,
+            &contract_addr,
+            amount,
+            plan,
+            "send",
+        )?;
+        let receive = Cw20ReceiveMsg {
+            sender: info.sender.into(),
+            amount: received,
+            msg,
+        };
+        Ok(response.add_message(WasmMsg::Execute {
+            contract_addr: contract,
+            msg: receive.into_binary()?,
+            funds: vec![],
+        }))
+    }
+
+    pub fn send_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract: String,
+        msg: Binary,
+        token_id: Uint128,
+    ) -> Result<Response<C>, ContractError> {
+        let response = self._transfer_nft(
+            deps,
+            env,
+            info.clone(),
+            info.sender.to_string(),
+            contract.clone(),
+            token_id,
+        )?;
+        Ok(response.add_message(
+            Cw721ReceiveMsg {
+                sender: info.sender.into(),
+                token_id: token_id.to_string(),
+                msg,
+            }
+            .into_cosmos_msg(contract)?,
+        ))
+    }
+
+    pub fn get_unit(&self, storage: &dyn Storage) -> Result<Uint128, ContractError> {
+        let decimals = self.decimals.load(storage)?;
+        Ok(Uint128::from(10u128).pow(decimals.into()))
+    }
+
+    /// Works out what a fungible transfer would do without writing anything:
+    /// runs the blocklist, balance and launch checks, takes the fee, and picks
+    /// the NFTs burned from `from` and the number minted to `to`.
+    pub fn plan_transfer(
+        &self,
+        storage: &dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        from: &Addr,
+        to: &Addr,
+        amount: Uint128,
+    ) -> Result<TransferPlan, ContractError> {
+        self.assert_not_blocked(storage, &[sender, from, to])?;
+        let unit = self.get_unit(storage)?;
+        let balance_before_sender = self.balances.may_load(storage, from)?.unwrap_or_default();
+        let balance_before_receiver = self.balances.may_load(storage, to)?.unwrap_or_default();
+        if balance_before_sender < amount {
+            return Err(ContractError::InsufficientBalance {
+                address: from.to_string(),
+                balance: balance_before_sender,
+                amount,
+            });
+        }
+        let fee = self.transfer_fee(storage, from, to, amount)?;
+        let received = match &fee {
+            Some((_, fee)) => amount - fee,
+            None => amount,
+        };
+        self.assert_launch_limits(
+            storage,
+            env,
+            from,
+            to,
+            amount,
+            received,
+            balance_before_receiver,
+        )?;
+
+        let mut plan = TransferPlan {
+            fee,
+            received,
+            burn_ids: vec![],
+            mint_count: 0,
+        };
+        // Moving funds to oneself changes nothing, and must not re-roll NFTs
+        if from == to {
+            return Ok(plan);
+        }
+
+        // Skip burn for certain addresses to save gas
+        if !self
+            .whitelist
+            .may_load(storage, from.to_string())?
+            .unwrap_or_default()
+        {
+            let tokens_to_burn =
+                (balance_before_sender / unit) - ((balance_before_sender - amount) / unit);
+            plan.burn_ids = self.burn_candidates(storage, from, tokens_to_burn.u128())?;
+        }
+        if !self
+            .whitelist
+            .may_load(storage, to.to_string())?
+            .unwrap_or_default()
+        {
+            plan.mint_count = ((balance_before_receiver + received) / unit
+                - balance_before_receiver / unit)
+                .u128();
+        }
+        Ok(plan)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn _transfer(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        from: String,
+        to: String,
+        amount: Uint128,
+        event: String,
+    ) -> Result<Response<C>, ContractError> {
+        let from_addr = deps.api.addr_validate(&from)?;
+        let to_addr = deps.api.addr_validate(&to)?;
+        let plan = self.plan_transfer(
+            deps.storage,
+            &env,
+            &info.sender,
+            &from_addr,
+            &to_addr,
+            amount,
+        )?;
+        self._apply_transfer(
+            deps.storage,
+            &env,
+            &from_addr,
+            &to_addr,
+            amount,
+            plan,
+            &event,
+        )
+    }
+
+    /// Writes a transfer worked out by `plan_transfer`.
+    #[allow(clippy::too_many_arguments)]
+    fn _apply_transfer(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        from_addr: &Addr,
+        to_addr: &Addr,
+        amount: Uint128,
+        plan: TransferPlan,
+        event: &str,
+    ) -> Result<Response<C>, ContractError> {
+        let mut response = Response::new()
+            .add_attribute("action", event)
+            .add_attribute("from", from_addr)
+            .add_attribute("to", to_addr)
+            .add_attribute("amount", amount);
+        if from_addr == to_addr {
+            return Ok(response);
+        }
+
+        let balance_before_sender = self
+            .balances
+            .may_load(storage, from_addr)?
+            .unwrap_or_default();
+        self._set_balance(
+            storage,
+            env.block.height,
+            from_addr,
+            balance_before_sender - amount,
+        )?;
+        for id in &plan.burn_ids {
+            self._burn_id(storage, env.block.height, from_addr, *id)?;
+        }
+        response = response.add_events(burn_events(from_addr, &plan.burn_ids));
+
+        let unit = self.get_unit(storage)?;
+        let mut hooks: Vec<_> = nft_hook(HookAction::Burn, from_addr, &plan.burn_ids, unit)
+            .into_iter()
+            .collect();
+
+        // NFTs are materialized from what each side holds after the fee
+        let minted = self._credit(storage, env, to_addr, plan.received)?;
+        response = response.add_events(mint_events(to_addr, &minted));
+        hooks.push(transfer_hook(from_addr, to_addr, plan.received));
+        hooks.extend(nft_hook(HookAction::Mint, to_addr, &minted, unit));
+
+        if let Some((fee_recipient, fee)) = plan.fee {
+            let minted = self._credit(storage, env, &fee_recipient, fee)?;
+            response = response
+                .add_attribute("fee", fee)
+                .add_attribute("fee_recipient", fee_recipient.to_string())
+                .add_events(mint_events(&fee_recipient, &minted));
+            hooks.push(transfer_hook(from_addr, &fee_recipient, fee));
+            hooks.extend(nft_hook(HookAction::Mint, &fee_recipient, &minted, unit));
+        }
+        Ok(response.add_submessages(self.hook_messages(storage, hooks)?))
+    }
+
+    /// Adds `amount` to the balance of `to` and mints the NFTs it now backs,
+    /// unless `to` is whitelisted. Returns the ids it minted.
+    fn _credit(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        to: &Addr,
+        amount: Uint128,
+    ) -> Result<Vec<Uint128>, ContractError> {
+        // Also covers the fee recipient, which the transfer never names
+        self.assert_not_blocked(storage, &[to])?;
+        let unit = self.get_unit(storage)?;
+        let balance_before = self.balances.may_load(storage, to)?.unwrap_or_default();
+        let balance_after = balance_before + amount;
+        self._set_balance(storage, env.block.height, to, balance_after)?;
+
+        // Skip minting for certain addresses to save gas
+        let mut minted = vec![];
+        if !self
+            .whitelist
+            .may_load(storage, to.to_string())?
+            .unwrap_or_default()
+        {
+            let tokens_to_mint = (balance_after / unit) - (balance_before / unit);
+            for _i in 0..tokens_to_mint.u128() {
+                minted.push(self._mint(storage, env.clone(), to.clone())?);
+            }
+        }
+        Ok(minted)
+    }
+
+    /// Mints the next token id to `to` and returns it.
+    pub fn _mint(
+        &self,
+        storage: &mut dyn Storage,
+        env: Env,
+        to: Addr,
+    ) -> Result<Uint128, ContractError> {
+        if to == "" {
+            return Err(ContractError::InvalidRecipient {
+                address: to.into_string(),
+            });
+        }
+        self.assert_not_blocked(storage, &[&to])?;
+
+        let minted = self.minted.load(storage)?;
+        let id = minted + Uint128::one();
+        self.minted.save(storage, &id)?;
+
+        let owner_of = self
+            .owner_of
+            .may_load(storage, id.to_string())?
+            .unwrap_or("".to_string());
+
+        if !owner_of.is_empty() {
+            return Err(ContractError::AlreadyExists {
+                token_id: id.to_string(),
+            });
+        }
+
+        self.owner_of
+            .save(storage, id.to_string(), &to.to_string())?;
+        self._push_owned(storage, env.block.height, to.as_str(), id)?;
+
+        let tier = _get_tier(&env);
+        let token_uri = self.tier_uri(storage, tier)?;
+        self.token_tiers.save(storage, id.to_string(), &tier)?;
+        self.tier_counts
+            .update(storage, tier, |count| -> StdResult<_> {
+                Ok(count.unwrap_or_default() + 1)
+            })?;
+        let extension = self
+            .tier_extensions
+            .may_load(storage, tier)?
+            .unwrap_or_default();
+        let token = TokenInfo {
+            owner: to.clone(),
+            approvals: vec![],
+            token_uri: Some(token_uri),
+            extension,
+        };
+
+        self.tokens.save(storage, &id.to_string(), &token)?;
+
+        Ok(id)
+    }
+
+    /// Burns the next NFT of `from` and returns its id.
+    pub fn _burn(
+        &self,
+        storage: &mut dyn Storage,
+        env: Env,
+        from: Addr,
+    ) -> Result<Uint128, ContractError> {
+        if from == "" {
+            return Err(ContractError::InvalidSender {
+                address: from.into_string(),
+            });
+        }
+
+        let id = self.burn_candidates(storage, &from, 1)?[0];
+        self._burn_id(storage, env.block.height, &from, id)?;
+        Ok(id)
+    }
+
+    /// Burns `id`, which `from` owns, and drops it and any NFTs `from` no
+    /// longer owns from its burn order.
+    fn _burn_id(
+        &self,
+        storage: &mut dyn Storage,
+        height: u64,
+        from: &Addr,
+        id: Uint128,
+    ) -> Result<(), ContractError> {
+        self._remove_owned(storage, height, from.as_str(), id)?;
+        self.owner_of.remove(storage, id.to_string());
+        self.get_approved.remove(storage, id.to_string());
+        self.tokens.remove(storage, &id.to_string())?;
+        if let Some(tier) = self.token_tiers.may_load(storage, id.to_string())? {
+            self.tier_counts
+                .update(storage, tier, |count| -> StdResult<_> {
+                    Ok(count.unwrap_or_default().saturating_sub(1))
+                })?;
+        }
+        self.token_tiers.remove(storage, id.to_string());
+        self.burned.update(storage, |burned| -> StdResult<_> {
+            Ok(burned + Uint128::one())
+        })?;
+
+        if let Some(order) = self.burn_order.may_load(storage, from.to_string())? {
+            let mut rest = vec![];
+            for entry in order {
+                if self
+                    .owner_of
+                    .may_load(storage, entry.to_string())?
+                    .as_deref()
+                    == Some(from.as_str())
+                {
+                    rest.push(entry);
+                }
+            }
+            if rest.is_empty() {
+                self.burn_order.remove(storage, from.to_string());
+            } else {
+                self.burn_order.save(storage, from.to_string(), &rest)?;
+            }
+        }
+        Ok(())
     }
 }
 
-fn approve_all(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    operator: String,
-) -> Result<Response, ContractError> {
-    deps.api.addr_validate(&operator)?;
-
-    APPROVED_FOR_ALL.save(
-        deps.storage,
-        (info.sender.to_string(), operator.clone()),
-        &true,
-    )?;
-
-    Ok(Response::new()
-        .add_attribute("action", "approve_all")
-        .add_attribute("sender", info.sender)
-        .add_attribute("operator", operator))
+/// Applies the cw20-base rules for name and symbol, and caps decimals at 18.
+fn validate_token_info(msg: &InstantiateMsg) -> Result<(), ContractError> {
+    validate_name(&msg.name)?;
+    validate_symbol(&msg.symbol)?;
+    if msg.decimals > 18 {
+        return Err(ContractError::InvalidDecimals {
+            decimals: msg.decimals,
+        });
+    }
+    Ok(())
 }
 
-fn revoke_all(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    operator: String,
-) -> Result<Response, ContractError> {
-    deps.api.addr_validate(&operator)?;
-
-    APPROVED_FOR_ALL.save(
-        deps.storage,
-        (info.sender.to_string(), operator.clone()),
-        &false,
-    )?;
-
-    Ok(Response::new()
-        .add_attribute("action", "revoke_all")
-        .add_attribute("sender", info.sender)
-        .add_attribute("operator", operator.to_string()))
+/// One `cw721_mint` event per NFT a call created for `to`, named like the
+/// attributes of `cw721_transfer`.
+fn mint_events(to: &Addr, token_ids: &[Uint128]) -> Vec<Event> {
+    token_ids
+        .iter()
+        .map(|id| {
+            Event::new("cw721_mint")
+                .add_attribute("to", to)
+                .add_attribute("token_id", id.to_string())
+        })
+        .collect()
 }
 
-fn transfer(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    from: String,
-    to: String,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    _transfer(deps, env, info, from, to, amount, "transfer".to_string())
+/// One `cw721_burn` event per NFT of `from` a call destroyed.
+fn burn_events(from: &Addr, token_ids: &[Uint128]) -> Vec<Event> {
+    token_ids
+        .iter()
+        .map(|id| {
+            Event::new("cw721_burn")
+                .add_attribute("from", from)
+                .add_attribute("token_id", id.to_string())
+        })
+        .collect()
 }
 
-fn send(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    from: String,
-    contract: String,
-    msg: Binary,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    let response = _transfer(
-        deps,
-        env,
-        info.clone(),
-        from,
-        contract.clone(),
+fn transfer_hook(from: &Addr, to: &Addr, amount: Uint128) -> Cw404HookMsg {
+    Cw404HookMsg {
+        action: HookAction::Transfer,
+        from: Some(from.to_string()),
+        to: Some(to.to_string()),
         amount,
-        "send".to_string(),
-    )
-    .unwrap();
-    Ok(response.add_message(
-        Cw20ReceiveMsg {
-            sender: info.sender.into(),
-            amount,
-            msg,
-        }
-        .into_cosmos_msg(contract)?,
-    ))
+        token_ids: vec![],
+    }
 }
 
-fn get_unit(storage: &dyn Storage) -> Result<Uint128, ContractError> {
-    let decimals = DECIMALS.load(storage)?;
-    Ok(Uint128::from(10u128).pow(decimals.into()))
+/// `Mint` or `Burn` hook for NFTs of `owner`, or nothing when there are none.
+fn nft_hook(
+    action: HookAction,
+    owner: &Addr,
+    token_ids: &[Uint128],
+    unit: Uint128,
+) -> Option<Cw404HookMsg> {
+    if token_ids.is_empty() {
+        return None;
+    }
+    let owner = Some(owner.to_string());
+    let (from, to) = match action {
+        HookAction::Mint => (None, owner),
+        _ => (owner, None),
+    };
+    Some(Cw404HookMsg {
+        action,
+        from,
+        to,
+        amount: unit * Uint128::from(token_ids.len() as u128),
+        token_ids: token_ids.iter().map(Uint128::to_string).collect(),
+    })
 }
 
-fn _transfer(
-    deps: DepsMut,
-    env: Env,
-    _info: MessageInfo,
-    from: String,
-    to: String,
-    amount: Uint128,
-    event: String,
-) -> Result<Response, ContractError> {
-    let from_addr = deps.api.addr_validate(&from)?;
-    let to_addr = deps.api.addr_validate(&to)?;
-    let unit = get_unit(deps.storage)?;
-    let balance_before_sender = BALANCES
-        .may_load(deps.storage, &from_addr)?
-        .unwrap_or_default();
-    let balance_before_receiver = BALANCES
-        .may_load(deps.storage, &to_addr)?
-        .unwrap_or_default();
-
-    BALANCES.update(
-        deps.storage,
-        &from_addr,
-        |balance: Option<Uint128>| -> StdResult<_> {
-            Ok(balance.unwrap_or_default().checked_sub(amount)?)
-        },
-    )?;
-
-    BALANCES.update(
-        deps.storage,
-        &to_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
-    )?;
-
-    let whitelist_from = WHITELIST
-        .may_load(deps.storage, from.clone())?
-        .unwrap_or_default();
-    let whitelist_to = WHITELIST
-        .may_load(deps.storage, to.clone())?
-        .unwrap_or_default();
-
-    // let mut messages = vec![];
-    // Skip burn for certain addresses to save gas
-    if !whitelist_from {
-        let tokens_to_burn = (balance_before_sender / unit)
-            - (BALANCES
-                .may_load(deps.storage, &from_addr)?
-                .unwrap_or_default()
-                / unit);
-        for _i in 0..tokens_to_burn.u128() {
-            // let msg = _burn(deps.storage, env.clone(), from_addr.clone())?;
-            _burn(deps.storage, env.clone(), from_addr.clone())?;
-            // messages.push(msg);
-        }
-    }
-
-    // Skip minting for certain addresses to save gas
-    if !whitelist_to {
-        let tokens_to_mint = (BALANCES
-            .may_load(deps.storage, &to_addr)?
-            .unwrap_or_default()
-            / unit)
-            - (balance_before_receiver / unit);
-        for _i in 0..tokens_to_mint.u128() {
-            // let msg = _mint(deps.storage, env.clone(), to_addr.clone())?;
-            _mint(deps.storage, env.clone(), to_addr.clone())?;
-            // messages.push(msg);
+/// Moves a maintained counter up or down by one.
+fn adjust_count(storage: &mut dyn Storage, count: &Item<u64>, increase: bool) -> StdResult<()> {
+    let current = count.may_load(storage)?.unwrap_or_default();
+    let next = if increase {
+        current + 1
+    } else {
+        current.saturating_sub(1)
+    };
+    count.save(storage, &next)
+}
+
+/// Checks embedded logos the way cw20-base does: size, then a light check of
+/// the SVG preamble or PNG header.
+fn verify_logo(logo: &Logo) -> Result<(), ContractError> {
+    let (data, is_svg) = match logo {
+        Logo::Url(_) => return Ok(()),
+        Logo::Embedded(EmbeddedLogo::Svg(data)) => (data, true),
+        Logo::Embedded(EmbeddedLogo::Png(data)) => (data, false),
+    };
+    if data.len() > LOGO_SIZE_CAP {
+        return Err(ContractError::LogoTooBig {
+            size: data.len(),
+            max: LOGO_SIZE_CAP,
+        });
+    }
+    if is_svg {
+        let preamble = String::from_utf8_lossy(data);
+        let preamble = preamble.trim_start();
+        if !preamble.starts_with("<?xml ") && !preamble.starts_with("<svg") {
+            return Err(ContractError::InvalidXmlPreamble {});
         }
+    } else if !data.starts_with(&PNG_HEADER) {
+        return Err(ContractError::InvalidPngHeader {});
     }
-
-    Ok(Response::new()
-        // .add_messages(messages)
-        .add_attribute("action", event.to_string())
-        .add_attribute("from", from)
-        .add_attribute("to", to)
-        .add_attribute("amount", amount))
+    Ok(())
 }
 
-fn _mint(storage: &mut dyn Storage, env: Env, to: Addr) -> Result<Response, ContractError> {
-    if to == "" {
-        return Err(ContractError::InvalidRecipient {});
-    }
-
-    let minted = MINTED.load(storage)?;
-    let id = minted + Uint128::one();
-    MINTED.save(storage, &id)?;
-
-    let owner_of = OWNER_OF
-        .may_load(storage, id.to_string())?
-        .unwrap_or("".to_string());
-
-    if owner_of != "" {
-        return Err(ContractError::AlreadyExists {});
-    }
-
-    OWNER_OF.save(storage, id.to_string(), &to.to_string())?;
-
-    let mut owned = OWNED.may_load(storage, to.to_string())?.unwrap_or(vec![]);
-    owned.push(id);
-    OWNED.save(storage, to.to_string(), &owned)?;
-    OWNED_INDEX.save(
-        storage,
-        id.to_string(),
-        &Uint128::from((owned.len() - 1) as u128),
-    )?;
-
-    let rng = _get_random_number(&env);
-    let token_uri;
-    if rng < Uint128::new(20){
-        token_uri = "https://arweave.net/XpXSyZiPGlpcc-Dsz7XMwdxKeNuczW-01uR5rNqOj3w";
-        
-    } else if rng < Uint128::new(40){
-        token_uri = "https://arweave.net/FRlxtstfBtzB_ocR8l7iJhU1vHTgljm68iGefpWKs4I";
-    } else if rng < Uint128::new(60) {
-        token_uri = "https://arweave.net/er-LhktIb_jZBPwUIHX0MGSGpYMz6OAP03bk74teGRg";
-    } else if rng < Uint128::new(80) {
-        token_uri = "https://arweave.net/DpzV3S9E-7FhMKSkDAImKH8mVYgh26g5ka1rZggelr8";
-    } else {
-        token_uri = "https://arweave.net/4vT1QhisR_8ENY9oCpz3X05qTLaMyPpkVQUSX6Ug6_I";
+fn validate_name(name: &str) -> Result<(), ContractError> {
+    if !(3..=50).contains(&name.len()) {
+        return Err(ContractError::InvalidName {
+            name: name.to_string(),
+        });
     }
-    TOKEN_URI.save(storage, id.to_string(), &token_uri.to_string())?;
-    let token = TokenInfo {
-            owner: to.clone(),
-            approvals: vec![],
-            token_uri: Some(token_uri.to_string()),
-            extension: Empty {},
-        };
+    Ok(())
+}
 
-    TOKENS.save(storage, &id.to_string(), &token)?;
+fn validate_symbol(symbol: &str) -> Result<(), ContractError> {
+    if !(3..=12).contains(&symbol.len())
+        || !symbol
+            .bytes()
+            .all(|byte| byte.is_ascii_alphabetic() || byte == b'-')
+    {
+        return Err(ContractError::InvalidSymbol {
+            symbol: symbol.to_string(),
+        });
+    }
+    Ok(())
+}
 
-    Ok(Response::new())
+fn parse_version(version: &str) -> Result<semver::Version, ContractError> {
+    version.parse().map_err(|_| ContractError::InvalidVersion {
+        version: version.to_string(),
+    })
 }
 
-fn _burn(storage: &mut dyn Storage, env: Env, from: Addr) -> Result<Response, ContractError> {
-    if from == "" {
-        return Err(ContractError::InvalidSender {});
-    }
-
-    let mut owned = OWNED.may_load(storage, from.to_string())?.unwrap_or(vec![]);
-    let id = owned[owned.len() - 1];
-    owned.pop();
-    OWNED.save(storage, from.to_string(), &owned)?;
-    OWNED_INDEX.remove(storage, id.to_string());
-    OWNER_OF.remove(storage, id.to_string());
-    GET_APPROVED.remove(storage, id.to_string());
-    TOKENS.remove(storage, &id.to_string())?;
-    // Prevents burning if user has locked their token
-    let locked = LOCKED.may_load(storage, id.to_string())?.unwrap_or(false);
-    if locked {
-        return Err(ContractError::PreventBurn {});
-    }
-    Ok(Response::new())
+fn _get_random_number(env: &Env) -> Uint128 {
+    let block_time = env.block.time.seconds(); // Using block time in seconds
+    let block_height = env.block.height; // Using block height
+
+    let seed = block_time as u128 + block_height as u128;
+    Uint128::from(seed % 100) // Adjust the range by changing the modulus
 }
 
+/// Splits the random range into `TIER_COUNT` equal buckets, numbered from 1.
+fn _get_tier(env: &Env) -> u8 {
+    let rng = _get_random_number(env);
+    (rng.u128() * u128::from(TIER_COUNT) / 100) as u8 + 1
+}
@@ -1,23 +1,27 @@
-// pub mod contract;
+pub mod contract;
 pub mod error;
 mod execute;
 pub mod msg;
 mod query;
 pub mod state;
+mod upgrades;
 
+pub use crate::contract::{Cw404Contract, StorageKeys};
 pub use crate::error::ContractError;
-pub use crate::msg::{ExecuteMsg, InstantiateMsg, MinterResponse, QueryMsg};
-
+pub use crate::msg::{InstantiateMsg, MigrateMsg, MinterResponse, QueryMsg};
+pub use cw404::{Extension, Metadata, Trait};
 
 use cosmwasm_std::Empty;
 
 // Version info for migration
 pub const CONTRACT_NAME: &str = "beepx:cw404";
-pub const CONTRACT_VERSION: &str = "0.1.0";
+pub const CONTRACT_VERSION: &str = "0.2.0";
+
+pub type ExecuteMsg = crate::msg::ExecuteMsg<Extension>;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -28,7 +32,8 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    crate::execute::instantiate(deps, env, info, msg)
+    let tract = Cw404Contract::<Extension, Empty>::default();
+    tract.instantiate(deps, env, info, msg)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -38,16 +43,24 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    crate::execute::execute(deps, env, info, msg)
+    let tract = Cw404Contract::<Extension, Empty>::default();
+    tract.execute(deps, env, info, msg)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    crate::query::query(deps, env, msg)
+    let tract = Cw404Contract::<Extension, Empty>::default();
+    tract.query(deps, env, msg)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let tract = Cw404Contract::<Extension, Empty>::default();
+    tract.reply(deps, env, msg)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let tract = Cw404Contract::<Extension, Empty>::default();
+    tract.migrate(deps, env, msg, CONTRACT_NAME, CONTRACT_VERSION)
+}
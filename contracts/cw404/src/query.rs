@@ -1,198 +1,891 @@
-use cw20::{BalanceResponse, TokenInfoResponse};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
-use cosmwasm_std::{to_json_binary, Addr, Binary, BlockInfo, Deps, Empty, Env, Order, StdResult, Uint128};
+use cw20::{
+    AllAccountsResponse, AllowanceInfo, BalanceResponse, DownloadLogoResponse, EmbeddedLogo, Logo,
+    MarketingInfoResponse, TokenInfoResponse,
+};
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, BlockInfo, CustomMsg, Deps, Empty, Env, Order, StdError,
+    StdResult, Storage, Uint128,
+};
 
-use cw721::{AllNftInfoResponse, NftInfoResponse, NumTokensResponse, OwnerOfResponse, TokensResponse};
-use cw_storage_plus::Bound;
+use cw721::{
+    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ContractInfoResponse, Cw721Query,
+    Expiration, NftInfoResponse, NumTokensResponse, OperatorResponse, OperatorsResponse,
+    OwnerOfResponse, TokensResponse,
+};
+use cw_storage_plus::{Bound, Map};
 
-use crate::msg::{ContractInfoResponse, MinterResponse, QueryMsg, UserInfoResponse};
-use crate::state::{
-    Approval, TokenInfo, BALANCES, CONTRACT_INFO, DECIMALS, LOCKED, MINTED, NAME, OWNED, OWNED_INDEX, OWNER_OF, SYMBOL, TOKENS, TOKEN_URI, TOTAL_SUPPLY
+use crate::contract::Cw404Contract;
+use crate::error::ContractError;
+use crate::execute::TIER_URIS;
+use crate::msg::{
+    AddressesResponse, BurnOrderResponse, FeeConfigResponse, HolderInfo, HoldersResponse, HookInfo,
+    HooksResponse, IsLockedResponse, LaunchStatusResponse, MetadataManagerResponse, MinterResponse,
+    NftCountResponse, OwnedToken, PauseStatusResponse, PauseTarget, PausedTarget, PayoutsResponse,
+    QueryMsg, Role, RoleMembersResponse, RolesResponse, SimulateTransferResponse, StatsResponse,
+    TierCount, TotalSupplyResponse, UserInfoResponse,
 };
+use crate::state::{Approval, TokenInfo, TIER_COUNT};
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 1000;
 
-fn contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
-    let contract_info = CONTRACT_INFO.load(deps.storage)?;
-    Ok(contract_info)
-}
+impl<'a, T, C> Cw721Query<T> for Cw404Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    fn contract_info(&self, deps: Deps) -> StdResult<ContractInfoResponse> {
+        let info = self.contract_info.load(deps.storage)?;
+        Ok(ContractInfoResponse {
+            name: info.name,
+            symbol: info.symbol,
+        })
+    }
 
-fn num_tokens(deps: Deps) -> StdResult<NumTokensResponse> {
-    let count = MINTED.may_load(deps.storage)?.unwrap_or(Uint128::zero());
-    Ok(NumTokensResponse {
-        count: count.u128() as u64,
-    })
-}
+    fn num_tokens(&self, deps: Deps) -> StdResult<NumTokensResponse> {
+        let count = self.live_nfts(deps)?;
+        Ok(NumTokensResponse {
+            count: count.u128() as u64,
+        })
+    }
 
-fn nft_info(deps: Deps, token_id: String) -> StdResult<NftInfoResponse> {
-    let base_uri = TOKEN_URI.load(deps.storage, token_id)?; // TODO: remove this line
-    Ok(NftInfoResponse {
-        token_uri: Some(base_uri),
-        extension: None,
-    })
-}
+    fn nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<T>> {
+        let info = self.tokens.load(deps.storage, &token_id)?;
+        Ok(NftInfoResponse {
+            token_uri: self.token_uri(deps, &token_id, info.token_uri)?,
+            extension: info.extension,
+        })
+    }
 
-fn owner_of(
-    deps: Deps,
-    _env: Env,
-    token_id: String,
-    _include_expired: bool,
-) -> StdResult<OwnerOfResponse> {
-    let owner = OWNER_OF
-        .may_load(deps.storage, token_id)?
-        .unwrap_or("".to_string());
-    Ok(OwnerOfResponse {
-        owner,
-        approvals: vec![],
-    })
-}
+    fn owner_of(
+        &self,
+        deps: Deps,
+        _env: Env,
+        token_id: String,
+        _include_expired: bool,
+    ) -> StdResult<OwnerOfResponse> {
+        let owner = self
+            .owner_of
+            .may_load(deps.storage, token_id)?
+            .unwrap_or("".to_string());
+        Ok(OwnerOfResponse {
+            owner,
+            approvals: vec![],
+        })
+    }
 
-fn user_info(deps: Deps, _env: Env, address: String) -> StdResult<UserInfoResponse> {
-    let owned = OWNED.may_load(deps.storage, address.clone())?.unwrap_or(vec![]);
-    let owned_index = OWNED_INDEX
-        .may_load(deps.storage, address.clone())?
-        .unwrap_or(Uint128::zero());
-    let balances = BALANCES
-        .may_load(deps.storage, &deps.api.addr_validate(&address)?)?
-        .unwrap_or(Uint128::zero());
-    Ok(UserInfoResponse {
-        owned,
-        owned_index,
-        balances,
-    })
-}
+    /// Operators approved through `ApproveAll` never expire.
+    fn operator(
+        &self,
+        deps: Deps,
+        _env: Env,
+        owner: String,
+        operator: String,
+        _include_expired: bool,
+    ) -> StdResult<OperatorResponse> {
+        let approved = self
+            .approved_for_all
+            .may_load(deps.storage, (owner, operator.clone()))?
+            .unwrap_or(false);
+        if !approved {
+            return Err(StdError::not_found("Approval not found"));
+        }
+        Ok(OperatorResponse {
+            approval: cw721::Approval {
+                spender: operator,
+                expires: Expiration::Never {},
+            },
+        })
+    }
+
+    fn operators(
+        &self,
+        deps: Deps,
+        _env: Env,
+        owner: String,
+        _include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let operators = self
+            .approved_for_all
+            .prefix(owner)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|item| matches!(item, Ok((_, true)) | Err(_)))
+            .take(limit)
+            .map(|item| {
+                item.map(|(spender, _)| cw721::Approval {
+                    spender,
+                    expires: Expiration::Never {},
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(OperatorsResponse { operators })
+    }
+
+    fn approval(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        spender: String,
+        include_expired: bool,
+    ) -> StdResult<ApprovalResponse> {
+        let info = self.tokens.load(deps.storage, &token_id)?;
+        let approved = self
+            .get_approved
+            .may_load(deps.storage, token_id)?
+            .unwrap_or_default();
+        if approved != spender {
+            return Err(StdError::not_found("Approval not found"));
+        }
+        let approval = humanize_approvals(&env.block, &info, true)
+            .into_iter()
+            .find(|apr| apr.spender == spender)
+            .unwrap_or(cw721::Approval {
+                spender,
+                expires: Expiration::Never {},
+            });
+        if !include_expired && approval.expires.is_expired(&env.block) {
+            return Err(StdError::not_found("Approval not found"));
+        }
+        Ok(ApprovalResponse { approval })
+    }
+
+    fn approvals(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<ApprovalsResponse> {
+        let info = self.tokens.load(deps.storage, &token_id)?;
+        Ok(ApprovalsResponse {
+            approvals: humanize_approvals(&env.block, &info, include_expired),
+        })
+    }
+
+    fn tokens(
+        &self,
+        deps: Deps,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let tokens: Vec<String> = self
+            .tokens
+            .idx
+            .owner
+            .prefix(owner_addr)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TokensResponse { tokens })
+    }
+
+    fn all_tokens(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
 
-fn is_locked(deps: Deps, _env: Env, token_id: String) -> StdResult<bool> {
-    let locked = LOCKED.may_load(deps.storage, token_id)?.unwrap_or(false);
-    Ok(locked)
+        let tokens: StdResult<Vec<String>> = self
+            .tokens
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(k, _)| k))
+            .collect();
+
+        Ok(TokensResponse { tokens: tokens? })
+    }
+
+    fn all_nft_info(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<AllNftInfoResponse<T>> {
+        let info = self.tokens.load(deps.storage, &token_id)?;
+        Ok(AllNftInfoResponse {
+            access: OwnerOfResponse {
+                owner: info.owner.to_string(),
+                approvals: humanize_approvals(&env.block, &info, include_expired),
+            },
+            info: NftInfoResponse {
+                token_uri: self.token_uri(deps, &token_id, info.token_uri)?,
+                extension: info.extension,
+            },
+        })
+    }
 }
 
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Minter {} => to_json_binary(&minter(deps)?),
-        QueryMsg::ContractInfo {} => to_json_binary(&contract_info(deps)?),
-        QueryMsg::Balance { address } => {
-            let user = deps.api.addr_validate(&address)?;
-            let balance = BALANCES
-                .may_load(deps.storage, &user)?
-                .unwrap_or(Uint128::zero());
-
-            to_json_binary(&BalanceResponse { balance })
+impl<'a, T, C> Cw404Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn user_info(
+        &self,
+        deps: Deps,
+        _env: Env,
+        address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<UserInfoResponse> {
+        let user = deps.api.addr_validate(&address)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|id| {
+                id.parse::<u128>()
+                    .map_err(|_| StdError::generic_err(format!("Invalid token id: {id}")))
+            })
+            .transpose()?;
+
+        let balance = self
+            .balances
+            .may_load(deps.storage, &user)?
+            .unwrap_or_default();
+        let decimals = self.decimals.load(deps.storage)?;
+        let unit = Uint128::from(10u128).pow(decimals.into());
+
+        let owned = self
+            .owned
+            .may_load(deps.storage, address.clone())?
+            .unwrap_or_default();
+        let nft_count = owned.len() as u32;
+        // Only the ids of the page are looked up: the `limit` lowest past
+        // `start_after`, ascending
+        let mut ids: Vec<Uint128> = owned
+            .into_iter()
+            .filter(|id| start_after.map_or(true, |start| id.u128() > start))
+            .collect();
+        if ids.len() > limit {
+            ids.select_nth_unstable(limit);
+            ids.truncate(limit);
+        }
+        ids.sort_unstable();
+        let mut page = Vec::with_capacity(ids.len());
+        let mut locked = vec![];
+        for id in ids {
+            let token_id = id.to_string();
+            if self
+                .locked
+                .may_load(deps.storage, token_id.clone())?
+                .unwrap_or(false)
+            {
+                locked.push(token_id.clone());
+            }
+            let tier = self
+                .token_tiers
+                .may_load(deps.storage, token_id.clone())?
+                .unwrap_or_default();
+            page.push(OwnedToken { token_id, tier });
         }
-        QueryMsg::TokenInfo {} => {
-            let name = NAME.load(deps.storage)?;
-            let symbol = SYMBOL.load(deps.storage)?;
-            let decimals = DECIMALS.load(deps.storage)?;
-            let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
-            to_json_binary(&TokenInfoResponse {
-                name,
-                symbol,
-                decimals,
-                total_supply,
+
+        let allowances = self
+            .allowance
+            .prefix(address.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(MAX_LIMIT as usize)
+            .map(|item| {
+                item.map(|(spender, allowance)| AllowanceInfo {
+                    spender,
+                    allowance,
+                    expires: Expiration::Never {},
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        let operators = self
+            .approved_for_all
+            .prefix(address.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(MAX_LIMIT as usize)
+            .filter_map(|item| match item {
+                Ok((operator, true)) => Some(Ok(operator)),
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
             })
+            .collect::<StdResult<_>>()?;
+
+        Ok(UserInfoResponse {
+            balance,
+            whole_units: balance / unit,
+            fractional: balance % unit,
+            nft_count,
+            owned: page,
+            locked,
+            exempt: self
+                .whitelist
+                .may_load(deps.storage, address)?
+                .unwrap_or(false),
+            blocked: self.blocklist.has(deps.storage, &user),
+            allowances,
+            operators,
+        })
+    }
+
+    /// Runs the checks and arithmetic of a `Transfer` sent by `from` without
+    /// writing, and reports failures in the response instead of erroring.
+    pub fn simulate_transfer(
+        &self,
+        deps: Deps,
+        env: Env,
+        from: String,
+        to: String,
+        amount: Uint128,
+    ) -> StdResult<SimulateTransferResponse>
+    where
+        T: Default,
+    {
+        let plan = (|| -> Result<_, ContractError> {
+            self.assert_not_paused(deps.storage, &env, PauseTarget::FungibleTransfers)?;
+            let from = deps.api.addr_validate(&from)?;
+            let to = deps.api.addr_validate(&to)?;
+            self.plan_transfer(deps.storage, &env, &from, &from, &to, amount)
+        })();
+        Ok(match plan {
+            Ok(plan) => SimulateTransferResponse {
+                error: None,
+                burned_ids: plan.burn_ids.iter().map(Uint128::to_string).collect(),
+                minted: plan.mint_count as u32,
+                fee: plan.fee.map_or(Uint128::zero(), |(_, fee)| fee),
+                received: plan.received,
+            },
+            Err(err) => SimulateTransferResponse {
+                error: Some(err.to_string()),
+                burned_ids: vec![],
+                minted: 0,
+                fee: Uint128::zero(),
+                received: Uint128::zero(),
+            },
+        })
+    }
+
+    pub fn is_locked(
+        &self,
+        deps: Deps,
+        _env: Env,
+        token_id: String,
+    ) -> StdResult<IsLockedResponse> {
+        let locked = self
+            .locked
+            .may_load(deps.storage, token_id)?
+            .unwrap_or(false);
+        Ok(IsLockedResponse { locked })
+    }
+
+    /// The first holder of the minter role, for cw721 compatibility.
+    /// Use `RoleMembers` to list every minter.
+    pub fn minter(&self, deps: Deps) -> StdResult<MinterResponse> {
+        let minter = self
+            .roles
+            .prefix(Role::Minter.as_str())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .transpose()?
+            .map(|a| a.into_string());
+
+        Ok(MinterResponse { minter })
+    }
+
+    /// Token URI of the NFTs in `tier`: the one set by `SetBaseTokenUri`, or
+    /// the built-in one.
+    pub fn tier_uri(&self, storage: &dyn Storage, tier: u8) -> StdResult<String> {
+        Ok(match self.tier_uris.may_load(storage, tier)? {
+            Some(uri) => uri,
+            None => TIER_URIS[usize::from(tier - 1)].to_string(),
+        })
+    }
+
+    /// NFTs follow the current URI of their tier. Tokens without a tier keep
+    /// the URI they were minted with.
+    fn token_uri(
+        &self,
+        deps: Deps,
+        token_id: &str,
+        minted_with: Option<String>,
+    ) -> StdResult<Option<String>> {
+        match self
+            .token_tiers
+            .may_load(deps.storage, token_id.to_string())?
+        {
+            Some(tier) => self.tier_uri(deps.storage, tier).map(Some),
+            None => Ok(minted_with),
         }
-        QueryMsg::NftInfo { token_id } => to_json_binary(&nft_info(deps, token_id)?),
-        QueryMsg::OwnerOf {
-            token_id,
-            include_expired,
-        } => to_json_binary(&owner_of(
-            deps,
-            env,
-            token_id,
-            include_expired.unwrap_or(false),
-        )?),
-        QueryMsg::UserInfo { address } => to_json_binary(&user_info(deps, env, address)?),
-        QueryMsg::AllNftInfo {
-            token_id,
-            include_expired,
-        } => to_json_binary(&all_nft_info(
-            deps,
-            env,
-            token_id,
-            include_expired.unwrap_or(false),
-        )?),
-        QueryMsg::NumTokens {} => to_json_binary(&num_tokens(deps)?),
-        QueryMsg::Ownership {  } => to_json_binary(&ownership(deps)?),
-        QueryMsg::Tokens {
-            owner,
-            start_after,
-            limit,
-        } => to_json_binary(&tokens(deps, owner, start_after, limit)?),
-        QueryMsg::AllTokens { start_after, limit } => {
-            to_json_binary(&all_tokens(deps, start_after, limit)?)
+    }
+
+    fn live_nfts(&self, deps: Deps) -> StdResult<Uint128> {
+        let minted = self.minted.may_load(deps.storage)?.unwrap_or_default();
+        let burned = self.burned.may_load(deps.storage)?.unwrap_or_default();
+        Ok(minted - burned)
+    }
+
+    pub fn stats(&self, deps: Deps) -> StdResult<StatsResponse> {
+        let total_supply = self.total_supply.load(deps.storage)?;
+        let inventory_balance = match self.sale_inventory.may_load(deps.storage)? {
+            Some(inventory) => self
+                .balances
+                .may_load(deps.storage, &inventory)?
+                .unwrap_or_default(),
+            None => Uint128::zero(),
+        };
+        let tiers = (1..=TIER_COUNT)
+            .map(|tier| {
+                let count = self
+                    .tier_counts
+                    .may_load(deps.storage, tier)?
+                    .unwrap_or_default();
+                Ok(TierCount { tier, count })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(StatsResponse {
+            total_supply,
+            total_native_supply: self.contract_info.load(deps.storage)?.total_supply,
+            circulating_supply: total_supply.saturating_sub(inventory_balance),
+            exempt_supply: self
+                .exempt_supply
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+            live_nfts: self.live_nfts(deps)?,
+            minted: self.minted.may_load(deps.storage)?.unwrap_or_default(),
+            burned: self.burned.may_load(deps.storage)?.unwrap_or_default(),
+            tiers,
+            holder_count: self
+                .holder_count
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+            nft_holder_count: self
+                .nft_holder_count
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn all_accounts(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAccountsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|s| deps.api.addr_validate(&s))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+
+        let accounts = self
+            .balances
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(Addr::into_string))
+            .collect::<StdResult<_>>()?;
+        Ok(AllAccountsResponse { accounts })
+    }
+
+    pub fn holders(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<HoldersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|s| deps.api.addr_validate(&s))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+
+        let holders = self
+            .balances
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (address, balance) = item?;
+                let nft_count = self
+                    .owned
+                    .may_load(deps.storage, address.to_string())?
+                    .map_or(0, |owned| owned.len() as u32);
+                let exempt = self
+                    .whitelist
+                    .may_load(deps.storage, address.to_string())?
+                    .unwrap_or(false);
+                Ok(HolderInfo {
+                    address: address.into_string(),
+                    balance,
+                    nft_count,
+                    exempt,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(HoldersResponse {
+            holders,
+            holder_count: self
+                .holder_count
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+            nft_holder_count: self
+                .nft_holder_count
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        })
+    }
+
+    /// `marketing` is the first holder of the marketing role.
+    pub fn marketing_info(&self, deps: Deps) -> StdResult<MarketingInfoResponse> {
+        let mut info = self
+            .marketing_info
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        info.marketing = self
+            .roles
+            .prefix(Role::Marketing.as_str())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .transpose()?;
+        Ok(info)
+    }
+
+    pub fn download_logo(&self, deps: Deps) -> StdResult<DownloadLogoResponse> {
+        match self.logo.may_load(deps.storage)? {
+            Some(Logo::Embedded(EmbeddedLogo::Svg(data))) => Ok(DownloadLogoResponse {
+                mime_type: "image/svg+xml".to_string(),
+                data,
+            }),
+            Some(Logo::Embedded(EmbeddedLogo::Png(data))) => Ok(DownloadLogoResponse {
+                mime_type: "image/png".to_string(),
+                data,
+            }),
+            _ => Err(StdError::not_found("logo")),
         }
+    }
 
+    pub fn metadata_manager(&self, deps: Deps) -> StdResult<MetadataManagerResponse> {
+        let managers = self
+            .role_members(deps, Role::MetadataManager, None, Some(MAX_LIMIT))?
+            .members;
+        let frozen = self
+            .metadata_frozen
+            .may_load(deps.storage)?
+            .unwrap_or(false);
+        Ok(MetadataManagerResponse { managers, frozen })
     }
-}
 
-fn all_nft_info(
-    deps: Deps,
-    env: Env,
-    token_id: String,
-    include_expired: bool,
-) -> StdResult<AllNftInfoResponse> {
-    let info = TOKENS.load(deps.storage, &token_id)?;
-    Ok(AllNftInfoResponse {
-        access: OwnerOfResponse {
-            owner: info.owner.to_string(),
-            approvals: humanize_approvals(&env.block, &info, include_expired),
-        },
-        info: NftInfoResponse {
-            token_uri: info.token_uri,
-            extension: None,
-        },
-    })
-}
+    pub fn role_members(
+        &self,
+        deps: Deps,
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<RoleMembersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|s| deps.api.addr_validate(&s))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
 
-fn all_tokens(
-    deps: Deps,
-    start_after: Option<String>,
-    limit: Option<u32>,
-) -> StdResult<TokensResponse> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+        let members = self
+            .roles
+            .prefix(role.as_str())
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|a| a.into_string()))
+            .collect::<StdResult<_>>()?;
+        Ok(RoleMembersResponse { role, members })
+    }
 
-    let tokens: StdResult<Vec<String>> = TOKENS
-        .range(deps.storage, start, None, Order::Ascending)
-        .take(limit)
-        .map(|item| item.map(|(k, _)| k))
-        .collect();
+    pub fn roles(&self, deps: Deps, address: String) -> StdResult<RolesResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let roles = Role::ALL
+            .into_iter()
+            .filter(|role| self.roles.has(deps.storage, (role.as_str(), &address)))
+            .collect();
+        Ok(RolesResponse { roles })
+    }
+
+    pub fn payouts(&self, deps: Deps) -> StdResult<PayoutsResponse> {
+        Ok(PayoutsResponse {
+            withdraw_address: self.withdraw_address.may_load(deps.storage)?,
+            payouts: self.payouts.may_load(deps.storage)?.unwrap_or_default(),
+        })
+    }
+
+    /// Not paged, the number of hooks is capped.
+    pub fn hooks(&self, deps: Deps) -> StdResult<HooksResponse> {
+        let hooks = self
+            .hooks
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(address, policy)| HookInfo {
+                    address: address.into_string(),
+                    policy,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(HooksResponse { hooks })
+    }
+
+    /// Pauses whose `until_height` has passed are left out.
+    pub fn pause_status(&self, deps: Deps, env: Env) -> StdResult<PauseStatusResponse> {
+        let mut paused = vec![];
+        for target in PauseTarget::ALL {
+            let until = match self.paused.may_load(deps.storage, target.as_str())? {
+                Some(until) if !until.is_expired(&env.block) => until,
+                _ => continue,
+            };
+            let until_height = match until {
+                Expiration::AtHeight(height) => Some(height),
+                _ => None,
+            };
+            paused.push(PausedTarget {
+                target,
+                until_height,
+            });
+        }
+        Ok(PauseStatusResponse { paused })
+    }
+
+    pub fn launch_status(&self, deps: Deps, env: Env) -> StdResult<LaunchStatusResponse> {
+        let config = self.launch_config.may_load(deps.storage)?;
+        let (trading_enabled, limits_active) = match &config {
+            Some(config) => {
+                let started = env.block.time >= config.trading_start;
+                let window_end = config.trading_start.plus_seconds(config.limits_duration);
+                (started, started && env.block.time < window_end)
+            }
+            None => (true, false),
+        };
+        Ok(LaunchStatusResponse {
+            config,
+            trading_enabled,
+            limits_active,
+        })
+    }
 
-    Ok(TokensResponse { tokens: tokens? })
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary>
+    where
+        T: Default,
+    {
+        match msg {
+            QueryMsg::Minter {} => to_json_binary(&self.minter(deps)?),
+            QueryMsg::MetadataManager {} => to_json_binary(&self.metadata_manager(deps)?),
+            QueryMsg::ContractInfo {} => to_json_binary(&self.contract_info.load(deps.storage)?),
+            QueryMsg::Balance { address } => {
+                let user = deps.api.addr_validate(&address)?;
+                let balance = self
+                    .balances
+                    .may_load(deps.storage, &user)?
+                    .unwrap_or(Uint128::zero());
+
+                to_json_binary(&BalanceResponse { balance })
+            }
+            QueryMsg::BalanceAtHeight { address, height } => {
+                let user = deps.api.addr_validate(&address)?;
+                let balance = self
+                    .balances
+                    .may_load_at_height(deps.storage, &user, height)?
+                    .unwrap_or_default();
+                to_json_binary(&BalanceResponse { balance })
+            }
+            QueryMsg::TotalSupplyAtHeight { height } => {
+                let total_supply = self
+                    .total_supply
+                    .may_load_at_height(deps.storage, height)?
+                    .unwrap_or_default();
+                to_json_binary(&TotalSupplyResponse { total_supply })
+            }
+            QueryMsg::NftCountAtHeight { address, height } => {
+                let user = deps.api.addr_validate(&address)?;
+                let count = self
+                    .nft_counts
+                    .may_load_at_height(deps.storage, &user, height)?
+                    .unwrap_or_default();
+                to_json_binary(&NftCountResponse { count })
+            }
+            QueryMsg::TokenInfo {} => {
+                let name = self.name.load(deps.storage)?;
+                let symbol = self.symbol.load(deps.storage)?;
+                let decimals = self.decimals.load(deps.storage)?;
+                let total_supply = self.total_supply.load(deps.storage)?;
+                to_json_binary(&TokenInfoResponse {
+                    name,
+                    symbol,
+                    decimals,
+                    total_supply,
+                })
+            }
+            QueryMsg::Stats {} => to_json_binary(&self.stats(deps)?),
+            QueryMsg::AllAccounts { start_after, limit } => {
+                to_json_binary(&self.all_accounts(deps, start_after, limit)?)
+            }
+            QueryMsg::Holders { start_after, limit } => {
+                to_json_binary(&self.holders(deps, start_after, limit)?)
+            }
+            QueryMsg::MarketingInfo {} => to_json_binary(&self.marketing_info(deps)?),
+            QueryMsg::DownloadLogo {} => to_json_binary(&self.download_logo(deps)?),
+            QueryMsg::NftInfo { token_id } => to_json_binary(&self.nft_info(deps, token_id)?),
+            QueryMsg::OwnerOf {
+                token_id,
+                include_expired,
+            } => to_json_binary(&self.owner_of(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::Approval {
+                token_id,
+                spender,
+                include_expired,
+            } => to_json_binary(&self.approval(
+                deps,
+                env,
+                token_id,
+                spender,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::Approvals {
+                token_id,
+                include_expired,
+            } => to_json_binary(&self.approvals(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::Operator {
+                owner,
+                operator,
+                include_expired,
+            } => to_json_binary(&self.operator(
+                deps,
+                env,
+                owner,
+                operator,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::AllOperators {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+            } => to_json_binary(&self.operators(
+                deps,
+                env,
+                owner,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+            )?),
+            QueryMsg::UserInfo {
+                address,
+                start_after,
+                limit,
+            } => to_json_binary(&self.user_info(deps, env, address, start_after, limit)?),
+            QueryMsg::SimulateTransfer { from, to, amount } => {
+                to_json_binary(&self.simulate_transfer(deps, env, from, to, amount)?)
+            }
+            QueryMsg::IsLocked { token_id } => {
+                to_json_binary(&self.is_locked(deps, env, token_id)?)
+            }
+            QueryMsg::BurnOrder { owner } => {
+                let mut token_ids = vec![];
+                for id in self
+                    .burn_order
+                    .may_load(deps.storage, owner.clone())?
+                    .unwrap_or_default()
+                {
+                    // Entries for NFTs that moved on are pruned lazily by `_burn`
+                    if self.owner_of.may_load(deps.storage, id.to_string())? == Some(owner.clone())
+                    {
+                        token_ids.push(id.to_string());
+                    }
+                }
+                to_json_binary(&BurnOrderResponse { token_ids })
+            }
+            QueryMsg::AllNftInfo {
+                token_id,
+                include_expired,
+            } => to_json_binary(&self.all_nft_info(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::NumTokens {} => to_json_binary(&self.num_tokens(deps)?),
+            QueryMsg::RoleMembers {
+                role,
+                start_after,
+                limit,
+            } => to_json_binary(&self.role_members(deps, role, start_after, limit)?),
+            QueryMsg::Roles { address } => to_json_binary(&self.roles(deps, address)?),
+            QueryMsg::Payouts {} => to_json_binary(&self.payouts(deps)?),
+            QueryMsg::PauseStatus {} => to_json_binary(&self.pause_status(deps, env)?),
+            QueryMsg::LaunchStatus {} => to_json_binary(&self.launch_status(deps, env)?),
+            QueryMsg::LaunchAllowlist { start_after, limit } => to_json_binary(&list_addresses(
+                deps,
+                &self.launch_allowlist,
+                start_after,
+                limit,
+            )?),
+            QueryMsg::FeeConfig {} => to_json_binary(&FeeConfigResponse {
+                config: self.fee_config.may_load(deps.storage)?,
+            }),
+            QueryMsg::FeeExempt { start_after, limit } => {
+                to_json_binary(&list_addresses(deps, &self.fee_exempt, start_after, limit)?)
+            }
+            QueryMsg::Blocklist { start_after, limit } => {
+                to_json_binary(&list_addresses(deps, &self.blocklist, start_after, limit)?)
+            }
+            QueryMsg::Pools { start_after, limit } => {
+                to_json_binary(&list_addresses(deps, &self.pools, start_after, limit)?)
+            }
+            QueryMsg::Hooks {} => to_json_binary(&self.hooks(deps)?),
+            QueryMsg::Tokens {
+                owner,
+                start_after,
+                limit,
+            } => to_json_binary(&self.tokens(deps, owner, start_after, limit)?),
+            QueryMsg::AllTokens { start_after, limit } => {
+                to_json_binary(&self.all_tokens(deps, start_after, limit)?)
+            }
+        }
+    }
 }
 
-fn tokens(
+/// Pages through the keys of an address set.
+fn list_addresses(
     deps: Deps,
-    owner: String,
+    set: &Map<&Addr, Empty>,
     start_after: Option<String>,
     limit: Option<u32>,
-) -> StdResult<TokensResponse> {
+) -> StdResult<AddressesResponse> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
 
-    let owner_addr = deps.api.addr_validate(&owner)?;
-    let tokens: Vec<String> =TOKENS
-        .idx
-        .owner
-        .prefix(owner_addr)
+    let addresses = set
         .keys(deps.storage, start, None, Order::Ascending)
         .take(limit)
-        .collect::<StdResult<Vec<_>>>()?;
-
-    Ok(TokensResponse { tokens })
-}
-
-pub fn minter(deps: Deps) -> StdResult<MinterResponse> {
-    let minter = cw_ownable::get_ownership(deps.storage)?
-        .owner
-        .map(|a| a.into_string());
-
-    Ok(MinterResponse { minter })
-}
-
-pub fn ownership(deps: Deps) -> StdResult<cw_ownable::Ownership<Addr>> {
-    cw_ownable::get_ownership(deps.storage)
+        .map(|item| item.map(|a| a.into_string()))
+        .collect::<StdResult<_>>()?;
+    Ok(AddressesResponse { addresses })
 }
 
 fn humanize_approvals<T>(
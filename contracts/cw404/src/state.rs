@@ -1,47 +1,23 @@
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
 
-use cosmwasm_std::{
-    Addr, BlockInfo, CustomMsg, Empty, Uint128
-};
+use cosmwasm_std::{Addr, BlockInfo};
 
 use cw721::Expiration;
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_storage_plus::{Index, IndexList, MultiIndex};
 
-use crate::msg::ContractInfoResponse;
-pub const MERKLE_ROOT: Item<String> = Item::new("merkle_root");
-pub const OWNER: Item<String> = Item::new("owner");
-pub const WITHDRAW_ADDRESS: Item<String>= Item::new("withdraw_address");
-pub const NAME: Item<String> = Item::new("name");
-pub const SYMBOL: Item<String> = Item::new("symbol");
-pub const TOKEN_URI: Map<String, String> = Map::new("token_uri");
-pub const DECIMALS: Item<u8> = Item::new("decimals");
-pub const TOTAL_SUPPLY: Item<Uint128> = Item::new("total_supply");
-pub const MINTED: Item<Uint128> = Item::new("minted");
-pub const WHITELIST: Map<String, bool> = Map::new("whitelist");
-/// Approval in native representation
-pub const GET_APPROVED: Map<String, String> = Map::new("get_approved");
-/// Allowance of user in fractional representation
-pub const ALLOWANCE: Map<(String, String), Uint128> = Map::new("cw20_allowance");
-pub const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
-/// Owner of a tokenID in native representation
-pub const OWNER_OF: Map<String, String> = Map::new("owner_of");
-/// Array of owned ids in native representation
-pub const OWNED: Map<String, Vec<Uint128>> = Map::new("owned");
-/// @dev Tracks indices for the _owned mapping
-pub const OWNED_INDEX: Map<String, Uint128> = Map::new("owned_index");
-pub const APPROVED_FOR_ALL: Map<(String, String), bool> = Map::new("approved_for_all");
+/// Number of NFT tiers `_mint` chooses between
+pub const TIER_COUNT: u8 = 5;
 
-pub const CONTRACT_INFO: Item<ContractInfoResponse> = Item::new("contract_info");
+/// Basis points making up a whole
+pub const BPS_DENOMINATOR: u16 = 10_000;
 
-pub const TOKENS: IndexedMap<'static, &'static str, TokenInfo<Empty>, TokenIndexes<'static,Empty>> = IndexedMap::new("tokens", TokenIndexes {
-    owner: MultiIndex::new(token_owner_idx, "tokens", "tokens__owner"),
-});
-
-pub const LOCKED: Map<String, bool> = Map::new("locked");
+/// Most hook contracts that can be registered, as every transfer calls each one
+pub const MAX_HOOKS: usize = 10;
 
+/// Reply id of hook submessages whose failures are ignored
+pub const HOOK_REPLY_ID: u64 = 1;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TokenInfo<T> {
@@ -82,4 +58,4 @@ where
 
 pub fn token_owner_idx<T>(_pk: &[u8], d: &TokenInfo<T>) -> Addr {
     d.owner.clone()
-}
\ No newline at end of file
+}
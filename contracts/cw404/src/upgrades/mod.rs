@@ -0,0 +1 @@
+pub mod v0_1;
@@ -0,0 +1,285 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    Addr, Attribute, CustomMsg, Empty, Order, StdError, StdResult, Storage, Uint128,
+};
+use cw2::ContractVersion;
+use cw_ownable::Ownership;
+use cw_storage_plus::{Item, Map};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::contract::Cw404Contract;
+use crate::error::ContractError;
+use crate::execute::TIER_URIS;
+use crate::msg::{ContractInfoResponse, Role};
+
+/// Deployments before 0.2.0 stored the instantiating address here. It was never
+/// read; ownership lived in `cw_ownable`.
+const LEGACY_OWNER: Item<String> = Item::new("owner");
+
+/// The `cw_ownable` owner, replaced by roles.
+const LEGACY_OWNERSHIP: Item<Ownership<Addr>> = Item::new("ownership");
+
+/// 0.1.x saved its contract info here after `cw2` had, replacing the version.
+const LEGACY_CONTRACT_INFO: Item<ContractInfoResponse> = Item::new("contract_info");
+
+/// Token URI of every NFT by id, written on mint and never read. Each token
+/// keeps its own copy.
+const LEGACY_TOKEN_URI: Map<String, String> = Map::new("token_uri");
+
+/// Version of a 0.1.x deployment, recognised by the contract info that replaced
+/// its `cw2` record. That record named `contract_name`, the only name 0.1.x used.
+pub fn stored_version(storage: &dyn Storage, contract_name: &str) -> Option<ContractVersion> {
+    LEGACY_CONTRACT_INFO
+        .may_load(storage)
+        .ok()
+        .flatten()
+        .map(|_| ContractVersion {
+            contract: contract_name.to_string(),
+            version: "0.1.0".to_string(),
+        })
+}
+
+/// Brings 0.1.x state up to the current layout and returns an attribute for
+/// every change made. NFT counts are recorded from `height`, earlier heights
+/// report none.
+pub fn migrate<T, C>(
+    storage: &mut dyn Storage,
+    height: u64,
+    contract: &Cw404Contract<T, C>,
+) -> Result<Vec<Attribute>, ContractError>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    let mut changes = vec![];
+
+    if !contract.contract_info.exists(storage) {
+        if let Some(info) = LEGACY_CONTRACT_INFO.may_load(storage)? {
+            contract.contract_info.save(storage, &info)?;
+            LEGACY_CONTRACT_INFO.remove(storage);
+            changes.push(Attribute::new("moved", "contract_info"));
+        }
+    }
+
+    if LEGACY_OWNER.exists(storage) {
+        LEGACY_OWNER.remove(storage);
+        changes.push(Attribute::new("removed", "owner"));
+    }
+
+    // Roles did not exist yet: the owner keeps every permission it had
+    let owner = LEGACY_OWNERSHIP
+        .may_load(storage)?
+        .and_then(|ownership| ownership.owner);
+    LEGACY_OWNERSHIP.remove(storage);
+    if let Some(owner) = owner {
+        for role in Role::ALL {
+            contract
+                .roles
+                .save(storage, (role.as_str(), &owner), &Empty {})?;
+        }
+        changes.push(Attribute::new("granted_all_roles", owner.clone()));
+
+        // The supply was minted to the owner, which sold from it directly
+        if !contract.sale_inventory.exists(storage) {
+            contract.sale_inventory.save(storage, &owner)?;
+            changes.push(Attribute::new("sale_inventory", owner));
+        }
+    }
+
+    if !contract.metadata_frozen.exists(storage) {
+        contract.metadata_frozen.save(storage, &false)?;
+        changes.push(Attribute::new("initialized", "metadata_frozen"));
+    }
+
+    if !contract.minted.exists(storage) {
+        contract.minted.save(storage, &Uint128::zero())?;
+        changes.push(Attribute::new("initialized", "minted"));
+    }
+
+    // Ids run from 1 to `minted`, so a live id above it means `minted` was
+    // lost, and the next mint would reuse an id
+    let mut highest = Uint128::zero();
+    for id in contract.tokens.keys(storage, None, None, Order::Ascending) {
+        highest = highest.max(Uint128::from_str(&id?)?);
+    }
+    if contract.minted.load(storage)? < highest {
+        contract.minted.save(storage, &highest)?;
+        changes.push(Attribute::new("repaired", "minted"));
+    }
+
+    // Transfers by id moved the last owned id into the freed slot without
+    // updating its index, and burns left empty lists behind
+    let owned: Vec<(String, Vec<Uint128>)> = contract
+        .owned
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (owner, ids) in owned {
+        if ids.is_empty() {
+            contract.owned.remove(storage, owner);
+            continue;
+        }
+        for (index, id) in ids.iter().enumerate() {
+            contract
+                .owned_index
+                .save(storage, id.to_string(), &Uint128::from(index as u128))?;
+        }
+    }
+    changes.push(Attribute::new("rebuilt", "owned_index"));
+
+    // The sale inventory must be whitelisted, or sales would burn NFTs it does
+    // not own, or its own. As `SetWhitelist` does, its NFTs are burned; the
+    // scans below then count them as burned and its balance as exempt.
+    if let Some(inventory) = contract.sale_inventory.may_load(storage)? {
+        let whitelisted = contract
+            .whitelist
+            .may_load(storage, inventory.to_string())?
+            .unwrap_or(false);
+        if !whitelisted {
+            let ids = contract
+                .owned
+                .may_load(storage, inventory.to_string())?
+                .unwrap_or_default();
+            for id in &ids {
+                contract.owner_of.remove(storage, id.to_string());
+                contract.owned_index.remove(storage, id.to_string());
+                contract.get_approved.remove(storage, id.to_string());
+                contract.locked.remove(storage, id.to_string());
+                contract.token_tiers.remove(storage, id.to_string());
+                contract.tokens.remove(storage, &id.to_string())?;
+            }
+            contract.owned.remove(storage, inventory.to_string());
+            contract
+                .whitelist
+                .save(storage, inventory.to_string(), &true)?;
+            changes.push(Attribute::new("whitelisted", inventory));
+            changes.push(Attribute::new(
+                "burned_inventory_nfts",
+                ids.len().to_string(),
+            ));
+        }
+    }
+
+    // Balances were saved even when they reached zero, which holder listings
+    // and counts now take as absent
+    let emptied: Vec<Addr> = contract
+        .balances
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((address, balance)) if balance.is_zero() => Some(Ok(address)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<_>>()?;
+    for address in &emptied {
+        contract.balances.remove(storage, address, height)?;
+    }
+    changes.push(Attribute::new("pruned_balances", emptied.len().to_string()));
+
+    // Counters did not exist yet, so they start from a full scan
+    if !contract.holder_count.exists(storage) {
+        let holders = contract
+            .balances
+            .keys(storage, None, None, Order::Ascending)
+            .count() as u64;
+        contract.holder_count.save(storage, &holders)?;
+        changes.push(Attribute::new("holder_count", holders.to_string()));
+    }
+
+    if !contract.nft_holder_count.exists(storage) {
+        let mut holders = 0u64;
+        for item in contract.owned.range(storage, None, None, Order::Ascending) {
+            if !item?.1.is_empty() {
+                holders += 1;
+            }
+        }
+        contract.nft_holder_count.save(storage, &holders)?;
+        changes.push(Attribute::new("nft_holder_count", holders.to_string()));
+    }
+
+    if contract
+        .nft_counts
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_none()
+    {
+        let counts: Vec<(String, u32)> = contract
+            .owned
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(owner, owned)| (owner, owned.len() as u32)))
+            .collect::<StdResult<_>>()?;
+        for (owner, count) in counts.iter().filter(|(_, count)| *count > 0) {
+            contract
+                .nft_counts
+                .save(storage, &Addr::unchecked(owner), count, height)?;
+        }
+        changes.push(Attribute::new("initialized", "nft_counts"));
+    }
+
+    // 0.1.x kept no tiers, only the token URI its tier mints with
+    if !contract.burned.exists(storage) {
+        let tokens: Vec<(String, Option<String>)> = contract
+            .tokens
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(id, token)| (id, token.token_uri)))
+            .collect::<StdResult<_>>()?;
+        for (id, token_uri) in &tokens {
+            let tier = match contract.token_tiers.may_load(storage, id.clone())? {
+                Some(tier) => tier,
+                None => match TIER_URIS
+                    .iter()
+                    .position(|uri| token_uri.as_deref() == Some(*uri))
+                {
+                    Some(index) => {
+                        let tier = index as u8 + 1;
+                        contract.token_tiers.save(storage, id.clone(), &tier)?;
+                        tier
+                    }
+                    None => continue,
+                },
+            };
+            contract
+                .tier_counts
+                .update(storage, tier, |count| -> StdResult<_> {
+                    Ok(count.unwrap_or_default() + 1)
+                })?;
+        }
+        let live = Uint128::new(tokens.len() as u128);
+        let burned = contract
+            .minted
+            .load(storage)?
+            .checked_sub(live)
+            .map_err(StdError::from)?;
+        contract.burned.save(storage, &burned)?;
+        changes.push(Attribute::new("burned", burned));
+    }
+
+    if !LEGACY_TOKEN_URI.is_empty(storage) {
+        LEGACY_TOKEN_URI.clear(storage);
+        changes.push(Attribute::new("removed", "token_uri"));
+    }
+
+    if !contract.exempt_supply.exists(storage) {
+        let whitelisted: Vec<String> = contract
+            .whitelist
+            .range(storage, None, None, Order::Ascending)
+            .filter_map(|item| match item {
+                Ok((address, true)) => Some(Ok(address)),
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
+            })
+            .collect::<StdResult<_>>()?;
+        let mut exempt = Uint128::zero();
+        for address in whitelisted {
+            exempt += contract
+                .balances
+                .may_load(storage, &Addr::unchecked(address))?
+                .unwrap_or_default();
+        }
+        contract.exempt_supply.save(storage, &exempt)?;
+        changes.push(Attribute::new("exempt_supply", exempt));
+    }
+
+    Ok(changes)
+}
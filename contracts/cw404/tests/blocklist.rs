@@ -0,0 +1,183 @@
+use cosmwasm_std::{coins, Binary, Event, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use cw404::{AddressesResponse, Cw404QueryMsg, FeeConfig};
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, CAROL, OWNER, PRICE, UNIT};
+
+fn block(address: &str, blocked: bool) -> ExecuteMsg {
+    ExecuteMsg::SetBlocklist {
+        address: address.to_string(),
+        blocked,
+    }
+}
+
+fn blocked(address: &str) -> ContractError {
+    ContractError::Blocked {
+        address: address.to_string(),
+    }
+}
+
+#[test]
+fn blocked_addresses_cannot_send_receive_approve_or_mint() {
+    let mut suite = Suite::new();
+    suite.transfer(OWNER, BOB, UNIT).unwrap();
+
+    assert_eq!(
+        suite.execute_err(ALICE, &block(BOB, true)),
+        ContractError::MissingRole {
+            role: "admin".to_string(),
+            address: ALICE.to_string(),
+        }
+    );
+    let res = suite.execute(OWNER, &block(BOB, true)).unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm-blocklist_update")
+            .add_attribute("address", BOB)
+            .add_attribute("blocked", "true")
+            .add_attribute("by", OWNER)
+    ));
+
+    // Send
+    assert_eq!(
+        suite
+            .transfer(BOB, CAROL, UNIT)
+            .unwrap_err()
+            .downcast::<ContractError>()
+            .unwrap(),
+        blocked(BOB)
+    );
+    assert_eq!(
+        suite.execute_err(
+            BOB,
+            &ExecuteMsg::TransferNft {
+                recipient: CAROL.to_string(),
+                token_id: "4".to_string(),
+            },
+        ),
+        blocked(BOB)
+    );
+    // Receive
+    assert_eq!(
+        suite
+            .transfer(ALICE, BOB, UNIT)
+            .unwrap_err()
+            .downcast::<ContractError>()
+            .unwrap(),
+        blocked(BOB)
+    );
+    assert_eq!(
+        suite.execute_err(
+            CAROL,
+            &ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: BOB.to_string(),
+                amount: Uint128::new(UNIT),
+                msg: Binary::default(),
+            }),
+        ),
+        blocked(BOB)
+    );
+    // Approve
+    assert_eq!(
+        suite.execute_err(
+            ALICE,
+            &ExecuteMsg::Approve {
+                spender: BOB.to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        ),
+        blocked(BOB)
+    );
+    // Mint
+    assert_eq!(
+        suite
+            .native_mint(PRICE)
+            .unwrap_err()
+            .downcast::<ContractError>()
+            .unwrap(),
+        blocked(BOB)
+    );
+    assert_eq!(
+        suite.app.wrap().query_all_balances(BOB).unwrap(),
+        coins(PRICE, "usei")
+    );
+
+    suite.execute(OWNER, &block(BOB, false)).unwrap();
+    suite.transfer(BOB, CAROL, UNIT).unwrap();
+    suite.native_mint(PRICE).unwrap();
+    assert_eq!(suite.balance(BOB), Uint128::new(UNIT));
+}
+
+#[test]
+fn blocked_addresses_get_no_nfts_from_whitelist_changes() {
+    let mut suite = Suite::new();
+    suite.transfer(OWNER, CAROL, 2 * UNIT).unwrap();
+    let whitelist = |state| ExecuteMsg::SetWhitelist {
+        target: CAROL.to_string(),
+        state,
+    };
+    suite.execute(OWNER, &whitelist(true)).unwrap();
+    suite.execute(OWNER, &block(CAROL, true)).unwrap();
+
+    assert_eq!(suite.execute_err(OWNER, &whitelist(false)), blocked(CAROL));
+    assert!(suite.tokens(CAROL).is_empty());
+
+    suite.execute(OWNER, &block(CAROL, false)).unwrap();
+    suite.execute(OWNER, &whitelist(false)).unwrap();
+    assert_eq!(suite.tokens(CAROL).len(), 2);
+}
+
+#[test]
+fn blocked_fee_recipients_are_not_credited() {
+    let mut suite = Suite::new();
+    let fee_config = ExecuteMsg::SetFeeConfig {
+        config: Some(FeeConfig {
+            recipient: CAROL.to_string(),
+            transfer_bps: 1_000,
+            buy_bps: 0,
+            sell_bps: 0,
+        }),
+    };
+    suite.execute(OWNER, &block(CAROL, true)).unwrap();
+    assert_eq!(suite.execute_err(OWNER, &fee_config), blocked(CAROL));
+
+    // Blocked after it was made the fee recipient
+    suite.execute(OWNER, &block(CAROL, false)).unwrap();
+    suite.execute(OWNER, &fee_config).unwrap();
+    suite.execute(OWNER, &block(CAROL, true)).unwrap();
+    assert_eq!(
+        suite
+            .transfer(ALICE, BOB, UNIT)
+            .unwrap_err()
+            .downcast::<ContractError>()
+            .unwrap(),
+        blocked(CAROL)
+    );
+    assert_eq!(suite.balance(CAROL), Uint128::zero());
+
+    suite.execute(OWNER, &block(CAROL, false)).unwrap();
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    assert_eq!(suite.balance(CAROL), Uint128::new(UNIT / 10));
+}
+
+#[test]
+fn blocklist_query_pages_addresses() {
+    let mut suite = Suite::new();
+    for address in [CAROL, ALICE, BOB] {
+        suite.execute(OWNER, &block(address, true)).unwrap();
+    }
+
+    let page = |start_after: Option<&str>, limit| {
+        let res: AddressesResponse = suite.query(&Cw404QueryMsg::Blocklist {
+            start_after: start_after.map(str::to_string),
+            limit,
+        });
+        res.addresses
+    };
+    assert_eq!(page(None, None), vec![ALICE, BOB, CAROL]);
+    assert_eq!(page(None, Some(2)), vec![ALICE, BOB]);
+    assert_eq!(page(Some(BOB), Some(2)), vec![CAROL]);
+}
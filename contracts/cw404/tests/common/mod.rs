@@ -0,0 +1,145 @@
+//! Fixtures shared by the cw404 multi-tests. Each test file only adds the
+//! setup specific to its scenarios.
+#![allow(dead_code)]
+
+use cosmwasm_std::{coins, Addr, Empty, Uint128};
+use cw20::{BalanceResponse, Cw20Coin};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use serde::de::DeserializeOwned;
+
+use cw404::{Cw404ExecuteMsg, Cw404QueryMsg};
+use cw404_base::{ContractError, InstantiateMsg};
+
+pub const OWNER: &str = "owner";
+pub const ALICE: &str = "alice";
+pub const BOB: &str = "bob";
+pub const CAROL: &str = "carol";
+
+/// One whole token with 6 decimals
+pub const UNIT: u128 = 1_000_000;
+
+/// Price of one token in `NativeMint`
+pub const PRICE: u128 = 50_000_000;
+
+pub type ExecuteMsg = Cw404ExecuteMsg<Empty>;
+
+pub fn cw404_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            cw404_base::execute,
+            cw404_base::instantiate,
+            cw404_base::query,
+        )
+        .with_reply(cw404_base::reply)
+        .with_migrate(cw404_base::migrate),
+    )
+}
+
+/// 100 tokens, of which Alice gets three, i.e. NFTs 1, 2 and 3. The rest
+/// stays with the owner as sale inventory.
+pub fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        name: "Beep".to_string(),
+        symbol: "BEEP".to_string(),
+        decimals: 6,
+        total_native_supply: Uint128::new(100),
+        minter: None,
+        initial_balances: vec![Cw20Coin {
+            address: ALICE.to_string(),
+            amount: Uint128::new(3 * UNIT),
+        }],
+        sale_inventory: None,
+    }
+}
+
+pub fn instantiate_err(msg: InstantiateMsg) -> ContractError {
+    let mut app = App::default();
+    let code_id = app.store_code(cw404_contract());
+    app.instantiate_contract(code_id, Addr::unchecked(OWNER), &msg, &[], "cw404", None)
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap()
+}
+
+pub struct Suite {
+    pub app: App,
+    pub token: Addr,
+}
+
+impl Suite {
+    /// Instantiates [`instantiate_msg`]. Bob starts with enough usei to buy once.
+    pub fn new() -> Self {
+        Self::with_msg(instantiate_msg())
+    }
+
+    pub fn with_msg(msg: InstantiateMsg) -> Self {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(BOB), coins(PRICE, "usei"))
+                .unwrap();
+        });
+        let code_id = app.store_code(cw404_contract());
+        let token = app
+            .instantiate_contract(code_id, Addr::unchecked(OWNER), &msg, &[], "cw404", None)
+            .unwrap();
+        Suite { app, token }
+    }
+
+    pub fn execute(&mut self, sender: &str, msg: &ExecuteMsg) -> anyhow::Result<AppResponse> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.token.clone(), msg, &[])
+    }
+
+    pub fn execute_err(&mut self, sender: &str, msg: &ExecuteMsg) -> ContractError {
+        self.execute(sender, msg)
+            .unwrap_err()
+            .downcast::<ContractError>()
+            .unwrap()
+    }
+
+    /// Buys one token for Bob, paying `amount` usei.
+    pub fn native_mint(&mut self, amount: u128) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(BOB),
+            self.token.clone(),
+            &ExecuteMsg::NativeMint {
+                recipient: BOB.to_string(),
+                merkle_proof: vec![],
+                hashed_address: vec![],
+            },
+            &coins(amount, "usei"),
+        )
+    }
+
+    pub fn transfer(&mut self, from: &str, to: &str, amount: u128) -> anyhow::Result<AppResponse> {
+        self.execute(
+            from,
+            &ExecuteMsg::Transfer {
+                recipient: to.to_string(),
+                amount: Uint128::new(amount),
+            },
+        )
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: &Cw404QueryMsg) -> T {
+        self.app.wrap().query_wasm_smart(&self.token, msg).unwrap()
+    }
+
+    pub fn balance(&self, address: &str) -> Uint128 {
+        let res: BalanceResponse = self.query(&Cw404QueryMsg::Balance {
+            address: address.to_string(),
+        });
+        res.balance
+    }
+
+    /// Token ids owned by `owner`, in storage key order.
+    pub fn tokens(&self, owner: &str) -> Vec<String> {
+        let res: cw721::TokensResponse = self.query(&Cw404QueryMsg::Tokens {
+            owner: owner.to_string(),
+            start_after: None,
+            limit: None,
+        });
+        res.tokens
+    }
+}
@@ -0,0 +1,61 @@
+use cw404::{ContractInfoResponse, Cw404QueryMsg};
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, OWNER};
+
+fn update(name: Option<&str>, description: Option<&str>) -> ExecuteMsg {
+    ExecuteMsg::UpdateContractInfo {
+        name: name.map(str::to_string),
+        symbol: None,
+        description: description.map(str::to_string),
+        image: None,
+        external_link: None,
+    }
+}
+
+fn contract_info(suite: &Suite) -> ContractInfoResponse {
+    suite.query(&Cw404QueryMsg::ContractInfo {})
+}
+
+#[test]
+fn admin_updates_contract_info_until_frozen() {
+    let mut suite = Suite::new();
+
+    assert_eq!(
+        suite.execute_err(ALICE, &update(Some("Boop"), None)),
+        ContractError::MissingRole {
+            role: "admin".to_string(),
+            address: ALICE.to_string(),
+        }
+    );
+    suite
+        .execute(OWNER, &update(Some("Boop"), Some("Beep boop")))
+        .unwrap();
+    let info = contract_info(&suite);
+    assert_eq!(info.name, "Boop");
+    assert_eq!(info.symbol, "BEEP");
+    assert_eq!(info.description.as_deref(), Some("Beep boop"));
+
+    // An empty description clears it, an unset name is kept
+    suite.execute(OWNER, &update(None, Some(""))).unwrap();
+    let info = contract_info(&suite);
+    assert_eq!(info.name, "Boop");
+    assert_eq!(info.description, None);
+    assert!(!info.frozen);
+
+    suite
+        .execute(OWNER, &ExecuteMsg::FreezeContractInfo {})
+        .unwrap();
+    assert_eq!(
+        suite.execute_err(OWNER, &update(Some("Beep"), None)),
+        ContractError::ContractInfoFrozen {}
+    );
+    assert_eq!(
+        suite.execute_err(OWNER, &ExecuteMsg::FreezeContractInfo {}),
+        ContractError::ContractInfoFrozen {}
+    );
+    let info = contract_info(&suite);
+    assert_eq!(info.name, "Boop");
+    assert!(info.frozen);
+}
@@ -0,0 +1,394 @@
+use cosmwasm_std::{to_json_binary, to_json_vec, Binary, Empty, Storage, Uint128};
+use cw20::{Cw20Coin, EmbeddedLogo, Logo};
+
+use cw404_base::{ContractError, InstantiateMsg};
+
+mod common;
+use common::{instantiate_err, instantiate_msg, ExecuteMsg, Suite, ALICE, BOB, OWNER, PRICE, UNIT};
+
+#[test]
+fn transfer_above_balance() {
+    let mut suite = Suite::new();
+
+    let err = suite.execute_err(
+        ALICE,
+        &ExecuteMsg::Transfer {
+            recipient: BOB.to_string(),
+            amount: Uint128::new(4 * UNIT),
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::InsufficientBalance {
+            address: ALICE.to_string(),
+            balance: Uint128::new(3 * UNIT),
+            amount: Uint128::new(4 * UNIT),
+        }
+    );
+}
+
+#[test]
+fn send_above_balance() {
+    let mut suite = Suite::new();
+
+    let err = suite.execute_err(
+        BOB,
+        &ExecuteMsg::Send {
+            contract: ALICE.to_string(),
+            amount: Uint128::new(UNIT),
+            msg: to_json_binary(&Empty {}).unwrap(),
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::InsufficientBalance {
+            address: BOB.to_string(),
+            balance: Uint128::zero(),
+            amount: Uint128::new(UNIT),
+        }
+    );
+}
+
+#[test]
+fn transfer_from_above_allowance() {
+    let mut suite = Suite::new();
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::IncreaseAllowance {
+                spender: BOB.to_string(),
+                amount: Uint128::new(UNIT),
+                expires: None,
+            },
+        )
+        .unwrap();
+
+    let err = suite.execute_err(
+        BOB,
+        &ExecuteMsg::TransferFrom {
+            owner: ALICE.to_string(),
+            recipient: BOB.to_string(),
+            amount: Uint128::new(2 * UNIT),
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::InsufficientAllowance {
+            owner: ALICE.to_string(),
+            spender: BOB.to_string(),
+            allowance: Uint128::new(UNIT),
+            amount: Uint128::new(2 * UNIT),
+        }
+    );
+}
+
+#[test]
+fn transfer_from_above_balance() {
+    let mut suite = Suite::new();
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::IncreaseAllowance {
+                spender: BOB.to_string(),
+                amount: Uint128::MAX,
+                expires: None,
+            },
+        )
+        .unwrap();
+
+    let err = suite.execute_err(
+        BOB,
+        &ExecuteMsg::TransferFrom {
+            owner: ALICE.to_string(),
+            recipient: BOB.to_string(),
+            amount: Uint128::new(4 * UNIT),
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::InsufficientBalance {
+            address: ALICE.to_string(),
+            balance: Uint128::new(3 * UNIT),
+            amount: Uint128::new(4 * UNIT),
+        }
+    );
+}
+
+#[test]
+fn transfer_from_nft_not_owned() {
+    let mut suite = Suite::new();
+
+    let err = suite.execute_err(
+        BOB,
+        &ExecuteMsg::TransferFrom {
+            owner: BOB.to_string(),
+            recipient: BOB.to_string(),
+            amount: Uint128::new(1),
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::NotTokenOwner {
+            token_id: "1".to_string(),
+            address: BOB.to_string(),
+        }
+    );
+}
+
+#[test]
+fn transfer_or_approve_nft_not_owned() {
+    let mut suite = Suite::new();
+
+    let unauthorized = ContractError::Unauthorized {
+        address: BOB.to_string(),
+        token_id: "1".to_string(),
+    };
+    let err = suite.execute_err(
+        BOB,
+        &ExecuteMsg::TransferFrom {
+            owner: ALICE.to_string(),
+            recipient: BOB.to_string(),
+            amount: Uint128::new(1),
+        },
+    );
+    assert_eq!(err, unauthorized);
+    let err = suite.execute_err(
+        BOB,
+        &ExecuteMsg::Approve {
+            spender: BOB.to_string(),
+            token_id: "1".to_string(),
+            expires: None,
+        },
+    );
+    assert_eq!(err, unauthorized);
+}
+
+#[test]
+fn transfer_nft_above_balance() {
+    let mut suite = Suite::new();
+    // State a 0.1.x deployment can hold: NFTs 1 to 3 backed by half a token
+    let key = [
+        length_prefixed(b"wasm"),
+        length_prefixed(format!("contract_data/{}", suite.token).as_bytes()),
+        length_prefixed(b"balance"),
+        ALICE.as_bytes().to_vec(),
+    ]
+    .concat();
+    let balance = to_json_vec(&Uint128::new(UNIT / 2)).unwrap();
+    suite.app.storage_mut().set(&key, &balance);
+
+    let err = suite.execute_err(
+        ALICE,
+        &ExecuteMsg::TransferNft {
+            recipient: BOB.to_string(),
+            token_id: "1".to_string(),
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::InsufficientBalance {
+            address: ALICE.to_string(),
+            balance: Uint128::new(UNIT / 2),
+            amount: Uint128::new(UNIT),
+        }
+    );
+}
+
+#[test]
+fn transfer_nft_to_whitelisted() {
+    let mut suite = Suite::new();
+
+    let err = suite.execute_err(
+        ALICE,
+        &ExecuteMsg::TransferNft {
+            recipient: OWNER.to_string(),
+            token_id: "1".to_string(),
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::WhitelistedRecipient {
+            address: OWNER.to_string(),
+        }
+    );
+}
+
+#[test]
+fn transfer_with_only_locked_nfts() {
+    let mut suite = Suite::new();
+    for token_id in ["1", "2", "3"] {
+        suite
+            .execute(
+                ALICE,
+                &ExecuteMsg::Lock {
+                    token_id: token_id.to_string(),
+                },
+            )
+            .unwrap();
+    }
+
+    let err = suite.execute_err(
+        ALICE,
+        &ExecuteMsg::Transfer {
+            recipient: BOB.to_string(),
+            amount: Uint128::new(UNIT),
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::PreventBurn {
+            owner: ALICE.to_string(),
+        }
+    );
+}
+
+#[test]
+fn native_mint_wrong_payment() {
+    let mut suite = Suite::new();
+
+    let err = suite
+        .native_mint(PRICE - 1)
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::IncorrectAmount {
+            denom: "usei".to_string(),
+            expected: Uint128::new(PRICE),
+            received: Uint128::new(PRICE - 1),
+        }
+    );
+}
+
+#[test]
+fn native_mint_from_empty_inventory() {
+    let mut suite = Suite::new();
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::Transfer {
+                recipient: ALICE.to_string(),
+                amount: Uint128::new(97 * UNIT),
+            },
+        )
+        .unwrap();
+
+    let err = suite
+        .native_mint(PRICE)
+        .unwrap_err()
+        .downcast::<ContractError>()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InsufficientBalance {
+            address: OWNER.to_string(),
+            balance: Uint128::zero(),
+            amount: Uint128::new(UNIT),
+        }
+    );
+}
+
+#[test]
+fn base_token_uri_unknown_tier() {
+    let mut suite = Suite::new();
+
+    for tier in [0, 6] {
+        let err = suite.execute_err(
+            OWNER,
+            &ExecuteMsg::SetBaseTokenUri {
+                id: tier,
+                uri: format!("ipfs://tier{tier}"),
+            },
+        );
+        assert_eq!(err, ContractError::InvalidTier { tier });
+    }
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetBaseTokenUri {
+                id: 5,
+                uri: "ipfs://tier5".to_string(),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn instantiate_invalid_symbol() {
+    let err = instantiate_err(InstantiateMsg {
+        symbol: "B3".to_string(),
+        ..instantiate_msg()
+    });
+    assert_eq!(
+        err,
+        ContractError::InvalidSymbol {
+            symbol: "B3".to_string(),
+        }
+    );
+}
+
+#[test]
+fn instantiate_supply_overflow() {
+    let err = instantiate_err(InstantiateMsg {
+        total_native_supply: Uint128::MAX,
+        ..instantiate_msg()
+    });
+    assert_eq!(
+        err,
+        ContractError::SupplyOverflow {
+            total_native_supply: Uint128::MAX,
+            decimals: 6,
+        }
+    );
+}
+
+#[test]
+fn instantiate_initial_balances_above_supply() {
+    let err = instantiate_err(InstantiateMsg {
+        initial_balances: vec![Cw20Coin {
+            address: ALICE.to_string(),
+            amount: Uint128::new(101 * UNIT),
+        }],
+        ..instantiate_msg()
+    });
+    assert_eq!(
+        err,
+        ContractError::InitialBalancesExceedSupply {
+            total: Uint128::new(101 * UNIT),
+            supply: Uint128::new(100 * UNIT),
+        }
+    );
+}
+
+#[test]
+fn upload_logo_oversized_or_malformed() {
+    let mut suite = Suite::new();
+
+    let err = suite.execute_err(
+        OWNER,
+        &ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Svg(Binary::from(
+            vec![b' '; 6 * 1024],
+        )))),
+    );
+    assert_eq!(
+        err,
+        ContractError::LogoTooBig {
+            size: 6 * 1024,
+            max: 5 * 1024,
+        }
+    );
+
+    let err = suite.execute_err(
+        OWNER,
+        &ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Png(Binary::from(
+            b"GIF89a".to_vec(),
+        )))),
+    );
+    assert_eq!(err, ContractError::InvalidPngHeader {});
+}
+
+/// Storage namespace as `cosmwasm-storage` and `cw-storage-plus` encode it
+fn length_prefixed(namespace: &[u8]) -> Vec<u8> {
+    [&(namespace.len() as u16).to_be_bytes(), namespace].concat()
+}
@@ -0,0 +1,89 @@
+use cosmwasm_std::Event;
+use cw_multi_test::AppResponse;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, OWNER, PRICE, UNIT};
+
+/// Every `kind` event of `res` as its `(token_id, from, to)` attributes
+fn nft_events(res: &AppResponse, kind: &str) -> Vec<(String, String, String)> {
+    let attr = |event: &Event, key: &str| {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map_or(String::new(), |attr| attr.value.clone())
+    };
+    res.events
+        .iter()
+        .filter(|event| event.ty == format!("wasm-{kind}"))
+        .map(|event| {
+            (
+                attr(event, "token_id"),
+                attr(event, "from"),
+                attr(event, "to"),
+            )
+        })
+        .collect()
+}
+
+fn event(token_id: &str, from: &str, to: &str) -> (String, String, String) {
+    (token_id.to_string(), from.to_string(), to.to_string())
+}
+
+#[test]
+fn transfer_emits_an_event_per_nft() {
+    let mut suite = Suite::new();
+
+    let res = suite.transfer(ALICE, BOB, 2 * UNIT).unwrap();
+    assert_eq!(
+        nft_events(&res, "cw721_burn"),
+        vec![event("3", ALICE, ""), event("2", ALICE, "")]
+    );
+    assert_eq!(
+        nft_events(&res, "cw721_mint"),
+        vec![event("4", "", BOB), event("5", "", BOB)]
+    );
+
+    let res = suite
+        .execute(
+            BOB,
+            &ExecuteMsg::TransferNft {
+                recipient: ALICE.to_string(),
+                token_id: "4".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        nft_events(&res, "cw721_transfer"),
+        vec![event("4", BOB, ALICE)]
+    );
+    assert!(nft_events(&res, "cw721_mint").is_empty());
+    assert!(nft_events(&res, "cw721_burn").is_empty());
+}
+
+#[test]
+fn sale_and_whitelist_emit_nft_events() {
+    let mut suite = Suite::new();
+
+    let res = suite.native_mint(PRICE).unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "transfer_from")));
+    assert_eq!(nft_events(&res, "cw721_mint"), vec![event("4", "", BOB)]);
+
+    let res = suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetWhitelist {
+                target: ALICE.to_string(),
+                state: true,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        nft_events(&res, "cw721_burn"),
+        vec![
+            event("3", ALICE, ""),
+            event("2", ALICE, ""),
+            event("1", ALICE, "")
+        ]
+    );
+}
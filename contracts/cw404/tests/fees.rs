@@ -0,0 +1,232 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+use cw_multi_test::{AppResponse, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+use cw404::FeeConfig;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, OWNER, PRICE, UNIT};
+
+const TREASURY: &str = "treasury";
+const POOL: &str = "pool";
+
+/// Contract that records the last cw20 `Receive` it got
+mod mock_receiver {
+    use super::*;
+
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        Receive(Cw20ReceiveMsg),
+    }
+
+    const RECEIVED: Item<Cw20ReceiveMsg> = Item::new("received");
+
+    pub fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    pub fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> StdResult<Response> {
+        let ExecuteMsg::Receive(received) = msg;
+        RECEIVED.save(deps.storage, &received)?;
+        Ok(Response::new())
+    }
+
+    pub fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&RECEIVED.load(deps.storage)?)
+    }
+}
+
+fn receiver_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        mock_receiver::execute,
+        mock_receiver::instantiate,
+        mock_receiver::query,
+    ))
+}
+
+/// 1% on transfers, 5% on buys from and 10% on sells to `POOL`
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetFeeConfig {
+                config: Some(FeeConfig {
+                    recipient: TREASURY.to_string(),
+                    transfer_bps: 100,
+                    buy_bps: 500,
+                    sell_bps: 1_000,
+                }),
+            },
+        )
+        .unwrap();
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetPool {
+                address: POOL.to_string(),
+                is_pool: true,
+            },
+        )
+        .unwrap();
+    suite
+}
+
+fn fee(res: &AppResponse) -> Option<String> {
+    res.events
+        .iter()
+        .filter(|event| event.ty == "wasm")
+        .flat_map(|event| &event.attributes)
+        .find(|attr| attr.key == "fee")
+        .map(|attr| attr.value.clone())
+}
+
+#[test]
+fn fee_is_taken_in_basis_points() {
+    let mut suite = setup();
+
+    let res = suite.transfer(ALICE, BOB, 2 * UNIT).unwrap();
+    assert_eq!(fee(&res).as_deref(), Some("20000"));
+    assert_eq!(suite.balance(BOB), Uint128::new(1_980_000));
+    assert_eq!(suite.balance(TREASURY), Uint128::new(20_000));
+    assert_eq!(suite.tokens(BOB), vec!["4"]);
+    assert!(suite.tokens(TREASURY).is_empty());
+
+    // Selling to the pool, then buying back from it
+    suite.transfer(ALICE, POOL, UNIT).unwrap();
+    assert_eq!(suite.balance(POOL), Uint128::new(900_000));
+    suite.transfer(POOL, BOB, 500_000).unwrap();
+    assert_eq!(suite.balance(BOB), Uint128::new(2_455_000));
+    assert_eq!(suite.balance(TREASURY), Uint128::new(145_000));
+}
+
+#[test]
+fn fee_rounds_down() {
+    let mut suite = setup();
+
+    let res = suite.transfer(ALICE, BOB, 199).unwrap();
+    assert_eq!(fee(&res).as_deref(), Some("1"));
+    assert_eq!(suite.balance(BOB), Uint128::new(198));
+
+    // 1% of 99 rounds to nothing, so no fee is recorded
+    let res = suite.transfer(ALICE, BOB, 99).unwrap();
+    assert_eq!(fee(&res), None);
+    assert_eq!(suite.balance(BOB), Uint128::new(297));
+    assert_eq!(suite.balance(TREASURY), Uint128::new(1));
+}
+
+#[test]
+fn exempt_transfers_pay_no_fee() {
+    let mut suite = setup();
+
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetFeeExempt {
+                address: ALICE.to_string(),
+                exempt: true,
+            },
+        )
+        .unwrap();
+    let res = suite.transfer(ALICE, BOB, UNIT).unwrap();
+    assert_eq!(fee(&res), None);
+    assert_eq!(suite.balance(BOB), Uint128::new(UNIT));
+
+    // Nor do payments to the fee recipient
+    let res = suite.transfer(BOB, TREASURY, UNIT / 2).unwrap();
+    assert_eq!(fee(&res), None);
+    assert_eq!(suite.balance(TREASURY), Uint128::new(UNIT / 2));
+}
+
+#[test]
+fn nft_transfers_pay_no_fee() {
+    let mut suite = setup();
+
+    let res = suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::TransferNft {
+                recipient: BOB.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(fee(&res), None);
+    assert_eq!(suite.balance(BOB), Uint128::new(UNIT));
+    assert_eq!(suite.tokens(BOB), vec!["1"]);
+
+    // Only the remainder beyond the listed NFTs pays
+    let res = suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::TransferWithIds {
+                recipient: BOB.to_string(),
+                amount: Uint128::new(3 * UNIT / 2),
+                token_ids: vec!["2".to_string()],
+            },
+        )
+        .unwrap();
+    assert_eq!(fee(&res).as_deref(), Some("5000"));
+    assert_eq!(suite.balance(BOB), Uint128::new(5 * UNIT / 2 - 5_000));
+    assert_eq!(suite.balance(TREASURY), Uint128::new(5_000));
+}
+
+#[test]
+fn sales_pay_no_fee() {
+    let mut suite = setup();
+
+    let res = suite.native_mint(PRICE).unwrap();
+    assert_eq!(fee(&res), None);
+    assert_eq!(suite.balance(BOB), Uint128::new(UNIT));
+    assert_eq!(suite.tokens(BOB), vec!["4"]);
+}
+
+#[test]
+fn send_reports_amount_after_fee() {
+    let mut suite = setup();
+    let code_id = suite.app.store_code(receiver_contract());
+    let receiver = suite
+        .app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "receiver",
+            None,
+        )
+        .unwrap();
+
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::Send {
+                contract: receiver.to_string(),
+                amount: Uint128::new(UNIT),
+                msg: Binary::default(),
+            },
+        )
+        .unwrap();
+    let received: Cw20ReceiveMsg = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&receiver, &Empty {})
+        .unwrap();
+    assert_eq!(received.amount, Uint128::new(990_000));
+    assert_eq!(suite.balance(receiver.as_str()), received.amount);
+}
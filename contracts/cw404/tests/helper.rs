@@ -0,0 +1,39 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::Executor;
+
+use cw404::Cw404Contract;
+
+mod common;
+use common::{Suite, ALICE, BOB, UNIT};
+
+#[test]
+fn helper_queries_and_builds_messages() {
+    let mut suite = Suite::new();
+    let token = Cw404Contract(suite.token.clone());
+    let querier = suite.app.wrap();
+
+    assert_eq!(
+        token.balance(&querier, ALICE).unwrap(),
+        Uint128::new(3 * UNIT)
+    );
+    assert_eq!(token.owner_of(&querier, "1", false).unwrap().owner, ALICE);
+    assert_eq!(
+        token.tokens(&querier, ALICE, None, Some(2)).unwrap().tokens,
+        vec!["1", "2"]
+    );
+
+    let msg = token.transfer(BOB, Uint128::new(UNIT)).unwrap();
+    suite.app.execute(Addr::unchecked(ALICE), msg).unwrap();
+    let msg = token.transfer_nft(BOB, "1").unwrap();
+    suite.app.execute(Addr::unchecked(ALICE), msg).unwrap();
+
+    let querier = suite.app.wrap();
+    assert_eq!(
+        token.balance(&querier, BOB).unwrap(),
+        Uint128::new(2 * UNIT)
+    );
+    assert_eq!(
+        token.tokens(&querier, BOB, None, None).unwrap().tokens,
+        vec!["1", "4"]
+    );
+}
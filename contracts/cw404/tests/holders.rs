@@ -0,0 +1,110 @@
+use cosmwasm_std::Uint128;
+use cw20::AllAccountsResponse;
+
+use cw404::{Cw404QueryMsg, HolderInfo, HoldersResponse};
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, CAROL, OWNER, UNIT};
+
+fn holders(suite: &Suite, start_after: Option<&str>, limit: Option<u32>) -> HoldersResponse {
+    suite.query(&Cw404QueryMsg::Holders {
+        start_after: start_after.map(str::to_string),
+        limit,
+    })
+}
+
+fn accounts(suite: &Suite, start_after: Option<&str>, limit: Option<u32>) -> Vec<String> {
+    let res: AllAccountsResponse = suite.query(&Cw404QueryMsg::AllAccounts {
+        start_after: start_after.map(str::to_string),
+        limit,
+    });
+    res.accounts
+}
+
+fn holder(address: &str, balance: u128, nft_count: u32, exempt: bool) -> HolderInfo {
+    HolderInfo {
+        address: address.to_string(),
+        balance: Uint128::new(balance),
+        nft_count,
+        exempt,
+    }
+}
+
+#[test]
+fn holders_follow_transfers_and_whitelisting() {
+    let mut suite = Suite::new();
+    let res = holders(&suite, None, None);
+    assert_eq!(
+        res.holders,
+        vec![
+            holder(ALICE, 3 * UNIT, 3, false),
+            holder(OWNER, 97 * UNIT, 0, true)
+        ]
+    );
+    assert_eq!((res.holder_count, res.nft_holder_count), (2, 1));
+
+    // A new holder
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    let res = holders(&suite, None, None);
+    assert_eq!(
+        res.holders,
+        vec![
+            holder(ALICE, 2 * UNIT, 2, false),
+            holder(BOB, UNIT, 1, false),
+            holder(OWNER, 97 * UNIT, 0, true),
+        ]
+    );
+    assert_eq!((res.holder_count, res.nft_holder_count), (3, 2));
+
+    // Bob's balance drops to zero, so he is no longer listed
+    suite.transfer(BOB, CAROL, UNIT).unwrap();
+    let res = holders(&suite, None, None);
+    assert_eq!(
+        res.holders,
+        vec![
+            holder(ALICE, 2 * UNIT, 2, false),
+            holder(CAROL, UNIT, 1, false),
+            holder(OWNER, 97 * UNIT, 0, true),
+        ]
+    );
+    assert_eq!((res.holder_count, res.nft_holder_count), (3, 2));
+    assert_eq!(accounts(&suite, None, None), vec![ALICE, CAROL, OWNER]);
+
+    // Whitelisting burns Alice's NFTs but keeps her balance
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetWhitelist {
+                target: ALICE.to_string(),
+                state: true,
+            },
+        )
+        .unwrap();
+    let res = holders(&suite, None, Some(1));
+    assert_eq!(res.holders, vec![holder(ALICE, 2 * UNIT, 0, true)]);
+    assert_eq!((res.holder_count, res.nft_holder_count), (3, 1));
+}
+
+#[test]
+fn holders_and_accounts_page_by_address() {
+    let mut suite = Suite::new();
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    suite.transfer(ALICE, CAROL, UNIT).unwrap();
+
+    let page = |start_after, limit| {
+        holders(&suite, start_after, limit)
+            .holders
+            .into_iter()
+            .map(|holder| holder.address)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(page(None, Some(2)), vec![ALICE, BOB]);
+    assert_eq!(page(Some(BOB), Some(2)), vec![CAROL, OWNER]);
+    assert!(page(Some(OWNER), None).is_empty());
+    // The counts cover every holder, whatever the page
+    let res = holders(&suite, Some(OWNER), None);
+    assert_eq!((res.holder_count, res.nft_holder_count), (4, 3));
+
+    assert_eq!(accounts(&suite, None, Some(3)), vec![ALICE, BOB, CAROL]);
+    assert_eq!(accounts(&suite, Some(CAROL), None), vec![OWNER]);
+}
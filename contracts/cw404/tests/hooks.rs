@@ -0,0 +1,190 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, Event, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
+use cw20::Cw20Coin;
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+use cw404::{Cw404HookMsg, HookAction, HookFailurePolicy};
+use cw404_base::InstantiateMsg;
+
+mod common;
+use common::{instantiate_msg, ExecuteMsg, Suite, ALICE, BOB, OWNER, UNIT};
+
+/// Hook contract that records every message it gets, or rejects them all
+mod mock_hook {
+    use super::*;
+
+    #[cw_serde]
+    pub struct InstantiateMsg {
+        pub fail: bool,
+    }
+
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        Cw404Hook(Cw404HookMsg),
+    }
+
+    const FAIL: Item<bool> = Item::new("fail");
+    const RECEIVED: Item<Vec<Cw404HookMsg>> = Item::new("received");
+
+    pub fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> StdResult<Response> {
+        FAIL.save(deps.storage, &msg.fail)?;
+        RECEIVED.save(deps.storage, &vec![])?;
+        Ok(Response::new())
+    }
+
+    pub fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> StdResult<Response> {
+        if FAIL.load(deps.storage)? {
+            return Err(StdError::generic_err("hook rejected"));
+        }
+        let ExecuteMsg::Cw404Hook(hook) = msg;
+        RECEIVED.update(deps.storage, |mut received| -> StdResult<_> {
+            received.push(hook);
+            Ok(received)
+        })?;
+        Ok(Response::new())
+    }
+
+    pub fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&RECEIVED.load(deps.storage)?)
+    }
+}
+
+fn hook_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        mock_hook::execute,
+        mock_hook::instantiate,
+        mock_hook::query,
+    ))
+}
+
+/// Alice starts with one and a half tokens, i.e. NFT 1
+fn setup() -> Suite {
+    Suite::with_msg(InstantiateMsg {
+        initial_balances: vec![Cw20Coin {
+            address: ALICE.to_string(),
+            amount: Uint128::new(3 * UNIT / 2),
+        }],
+        ..instantiate_msg()
+    })
+}
+
+/// Registers a new mock hook with `policy`
+fn add_hook(suite: &mut Suite, fail: bool, policy: HookFailurePolicy) -> Addr {
+    let code_id = suite.app.store_code(hook_contract());
+    let hook = suite
+        .app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &mock_hook::InstantiateMsg { fail },
+            &[],
+            "hook",
+            None,
+        )
+        .unwrap();
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::AddHook {
+                address: hook.to_string(),
+                policy,
+            },
+        )
+        .unwrap();
+    hook
+}
+
+fn received(suite: &Suite, hook: &Addr) -> Vec<Cw404HookMsg> {
+    suite.app.wrap().query_wasm_smart(hook, &Empty {}).unwrap()
+}
+
+#[test]
+fn hooks_see_transfers_mints_and_burns() {
+    let mut suite = setup();
+    let hook = add_hook(&mut suite, false, HookFailurePolicy::Revert);
+
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    suite
+        .execute(
+            BOB,
+            &ExecuteMsg::TransferNft {
+                recipient: ALICE.to_string(),
+                token_id: "2".to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        received(&suite, &hook),
+        vec![
+            Cw404HookMsg {
+                action: HookAction::Burn,
+                from: Some(ALICE.to_string()),
+                to: None,
+                amount: Uint128::new(UNIT),
+                token_ids: vec!["1".to_string()],
+            },
+            Cw404HookMsg {
+                action: HookAction::Transfer,
+                from: Some(ALICE.to_string()),
+                to: Some(BOB.to_string()),
+                amount: Uint128::new(UNIT),
+                token_ids: vec![],
+            },
+            Cw404HookMsg {
+                action: HookAction::Mint,
+                from: None,
+                to: Some(BOB.to_string()),
+                amount: Uint128::new(UNIT),
+                token_ids: vec!["2".to_string()],
+            },
+            Cw404HookMsg {
+                action: HookAction::NftTransfer,
+                from: Some(BOB.to_string()),
+                to: Some(ALICE.to_string()),
+                amount: Uint128::new(UNIT),
+                token_ids: vec!["2".to_string()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn failing_hook_reverts_or_is_ignored() {
+    let mut suite = setup();
+    let hook = add_hook(&mut suite, true, HookFailurePolicy::Revert);
+    let transfer = ExecuteMsg::Transfer {
+        recipient: BOB.to_string(),
+        amount: Uint128::new(UNIT),
+    };
+
+    suite.execute(ALICE, &transfer).unwrap_err();
+    assert_eq!(suite.balance(BOB), Uint128::zero());
+
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::AddHook {
+                address: hook.to_string(),
+                policy: HookFailurePolicy::Ignore,
+            },
+        )
+        .unwrap();
+    let res = suite.execute(ALICE, &transfer).unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "hook_failed")));
+    assert_eq!(suite.balance(BOB), Uint128::new(UNIT));
+}
@@ -0,0 +1,146 @@
+use cosmwasm_std::Uint128;
+
+use cw404::LaunchConfig;
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, CAROL, OWNER, UNIT};
+
+const POOL: &str = "pool";
+
+/// Seconds until trading starts
+const DELAY: u64 = 100;
+
+/// Seconds the launch limits apply once trading started
+const DURATION: u64 = 1_000;
+
+/// Trading starts [`DELAY`] seconds from now, with at most two tokens per
+/// transfer and three per wallet during the launch window.
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    let config = LaunchConfig {
+        trading_start: suite.app.block_info().time.plus_seconds(DELAY),
+        limits_duration: DURATION,
+        max_tx_amount: Some(Uint128::new(2 * UNIT)),
+        max_wallet_amount: Some(Uint128::new(3 * UNIT)),
+    };
+    assert_eq!(
+        suite.execute_err(
+            ALICE,
+            &ExecuteMsg::SetLaunchConfig {
+                config: config.clone(),
+            },
+        ),
+        ContractError::MissingRole {
+            role: "admin".to_string(),
+            address: ALICE.to_string(),
+        }
+    );
+    suite
+        .execute(OWNER, &ExecuteMsg::SetLaunchConfig { config })
+        .unwrap();
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetPool {
+                address: POOL.to_string(),
+                is_pool: true,
+            },
+        )
+        .unwrap();
+    suite
+}
+
+fn advance(suite: &mut Suite, seconds: u64) {
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(seconds));
+}
+
+fn transfer_err(suite: &mut Suite, from: &str, to: &str, amount: u128) -> ContractError {
+    suite
+        .transfer(from, to, amount)
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+}
+
+#[test]
+fn only_allowlist_and_inventory_trade_before_start() {
+    let mut suite = setup();
+
+    assert_eq!(
+        transfer_err(&mut suite, ALICE, BOB, UNIT),
+        ContractError::TradingNotStarted {}
+    );
+    suite.transfer(OWNER, BOB, UNIT).unwrap();
+
+    let allow = |allowed| ExecuteMsg::SetLaunchAllowlist {
+        address: ALICE.to_string(),
+        allowed,
+    };
+    suite.execute(OWNER, &allow(true)).unwrap();
+    suite.transfer(ALICE, CAROL, UNIT).unwrap();
+    suite.execute(OWNER, &allow(false)).unwrap();
+    assert_eq!(
+        transfer_err(&mut suite, ALICE, CAROL, UNIT),
+        ContractError::TradingNotStarted {}
+    );
+
+    advance(&mut suite, DELAY);
+    suite.transfer(ALICE, CAROL, UNIT).unwrap();
+    assert_eq!(suite.balance(CAROL), Uint128::new(2 * UNIT));
+}
+
+#[test]
+fn limits_apply_during_the_launch_window() {
+    let mut suite = setup();
+    advance(&mut suite, DELAY);
+
+    assert_eq!(
+        transfer_err(&mut suite, ALICE, BOB, 3 * UNIT),
+        ContractError::MaxTxExceeded {
+            amount: Uint128::new(3 * UNIT),
+            max: Uint128::new(2 * UNIT),
+        }
+    );
+    // The limit caps the whole call, not each NFT it moves
+    assert_eq!(
+        suite.execute_err(
+            ALICE,
+            &ExecuteMsg::TransferWithIds {
+                recipient: BOB.to_string(),
+                amount: Uint128::new(3 * UNIT),
+                token_ids: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            },
+        ),
+        ContractError::MaxTxExceeded {
+            amount: Uint128::new(3 * UNIT),
+            max: Uint128::new(2 * UNIT),
+        }
+    );
+    // Sales out of the inventory skip the max-transaction limit only
+    suite.transfer(OWNER, BOB, 3 * UNIT).unwrap();
+    assert_eq!(
+        transfer_err(&mut suite, ALICE, BOB, UNIT),
+        ContractError::MaxWalletExceeded {
+            address: BOB.to_string(),
+            balance: Uint128::new(4 * UNIT),
+            max: Uint128::new(3 * UNIT),
+        }
+    );
+    // Selling into a pool skips both limits, buying out of one does not
+    suite.transfer(ALICE, POOL, 3 * UNIT).unwrap();
+    assert_eq!(
+        transfer_err(&mut suite, POOL, CAROL, 3 * UNIT),
+        ContractError::MaxTxExceeded {
+            amount: Uint128::new(3 * UNIT),
+            max: Uint128::new(2 * UNIT),
+        }
+    );
+    suite.transfer(POOL, CAROL, 2 * UNIT).unwrap();
+
+    advance(&mut suite, DURATION);
+    suite.transfer(OWNER, BOB, 5 * UNIT).unwrap();
+    assert_eq!(suite.balance(BOB), Uint128::new(8 * UNIT));
+}
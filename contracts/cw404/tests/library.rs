@@ -0,0 +1,117 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+
+use cw404::{Cw404ExecuteMsg, Cw404QueryMsg};
+mod common;
+use common::{instantiate_msg, Suite, ALICE, BOB, OWNER, UNIT};
+
+/// Contract embedding cw404 with plain `Empty` NFT extensions and its own
+/// balance namespace
+mod embedded {
+    use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+
+    use cw404::{Cw404ExecuteMsg, Cw404QueryMsg};
+    use cw404_base::{ContractError, Cw404Contract, InstantiateMsg, StorageKeys};
+
+    pub const BALANCES: &str = "embedded_balance";
+
+    fn contract() -> Cw404Contract<'static, Empty, Empty> {
+        Cw404Contract::new(StorageKeys {
+            balances: BALANCES,
+            balances_checkpoints: "embedded_balance__checkpoints",
+            balances_changelog: "embedded_balance__changelog",
+            ..StorageKeys::default()
+        })
+    }
+
+    pub fn instantiate(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> Result<Response, ContractError> {
+        contract().instantiate(deps, env, info, msg)
+    }
+
+    pub fn execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw404ExecuteMsg<Empty>,
+    ) -> Result<Response, ContractError> {
+        contract().execute(deps, env, info, msg)
+    }
+
+    pub fn query(deps: Deps, env: Env, msg: Cw404QueryMsg) -> StdResult<Binary> {
+        contract().query(deps, env, msg)
+    }
+}
+
+fn embedded_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        embedded::execute,
+        embedded::instantiate,
+        embedded::query,
+    ))
+}
+
+fn setup() -> Suite {
+    let mut app = App::default();
+    let code_id = app.store_code(embedded_contract());
+    let token = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &instantiate_msg(),
+            &[],
+            "embedded",
+            None,
+        )
+        .unwrap();
+    Suite { app, token }
+}
+
+#[test]
+fn embedded_contract_uses_its_storage_keys() {
+    let mut suite = setup();
+
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    assert_eq!(suite.balance(BOB), Uint128::new(UNIT));
+    assert_eq!(suite.tokens(BOB), vec!["4"]);
+
+    let balance = |namespace: &str| {
+        let key = Map::<&Addr, Uint128>::new(namespace).key(&Addr::unchecked(BOB));
+        suite
+            .app
+            .wrap()
+            .query_wasm_raw(&suite.token, key.to_vec())
+            .unwrap()
+    };
+    assert!(balance(embedded::BALANCES).is_some());
+    assert_eq!(balance("balance"), None);
+}
+
+#[test]
+fn embedded_contract_serves_its_extension() {
+    let mut suite = setup();
+
+    suite
+        .execute(
+            ALICE,
+            &Cw404ExecuteMsg::<Empty>::TransferNft {
+                recipient: BOB.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let res: cw721::NftInfoResponse<Empty> = suite.query(&Cw404QueryMsg::NftInfo {
+        token_id: "1".to_string(),
+    });
+    assert_eq!(res.extension, Empty {});
+    let res: cw721::OwnerOfResponse = suite.query(&Cw404QueryMsg::OwnerOf {
+        token_id: "1".to_string(),
+        include_expired: None,
+    });
+    assert_eq!(res.owner, BOB);
+}
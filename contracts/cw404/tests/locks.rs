@@ -0,0 +1,88 @@
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, CAROL, UNIT};
+
+fn lock(token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::Lock {
+        token_id: token_id.to_string(),
+    }
+}
+
+fn burn_order(token_ids: &[&str]) -> ExecuteMsg {
+    ExecuteMsg::SetBurnOrder {
+        token_ids: token_ids.iter().map(|id| id.to_string()).collect(),
+    }
+}
+
+#[test]
+fn burns_follow_the_order_and_skip_locked_nfts() {
+    let mut suite = Suite::new();
+
+    assert_eq!(
+        suite.execute_err(BOB, &lock("1")),
+        ContractError::Unauthorized {
+            address: BOB.to_string(),
+            token_id: "1".to_string(),
+        }
+    );
+    assert_eq!(
+        suite.execute_err(BOB, &burn_order(&["1"])),
+        ContractError::Unauthorized {
+            address: BOB.to_string(),
+            token_id: "1".to_string(),
+        }
+    );
+
+    suite.execute(ALICE, &burn_order(&["1", "2"])).unwrap();
+    suite.execute(ALICE, &lock("1")).unwrap();
+
+    suite.transfer(ALICE, CAROL, UNIT).unwrap();
+    assert_eq!(suite.tokens(ALICE), vec!["1", "3"]);
+    // Past the order, unlocked NFTs burn newest first
+    suite.transfer(ALICE, CAROL, UNIT).unwrap();
+    assert_eq!(suite.tokens(ALICE), vec!["1"]);
+}
+
+#[test]
+fn fully_locked_owner_cannot_burn() {
+    let mut suite = Suite::new();
+    for token_id in ["1", "2", "3"] {
+        suite.execute(ALICE, &lock(token_id)).unwrap();
+    }
+
+    assert_eq!(
+        suite
+            .transfer(ALICE, BOB, UNIT)
+            .unwrap_err()
+            .downcast::<ContractError>()
+            .unwrap(),
+        ContractError::PreventBurn {
+            owner: ALICE.to_string(),
+        }
+    );
+
+    // Moving an NFT clears its lock
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::TransferNft {
+                recipient: BOB.to_string(),
+                token_id: "3".to_string(),
+            },
+        )
+        .unwrap();
+    suite.transfer(BOB, CAROL, UNIT).unwrap();
+    assert!(suite.tokens(BOB).is_empty());
+
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::Unlock {
+                token_id: "2".to_string(),
+            },
+        )
+        .unwrap();
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    assert_eq!(suite.tokens(ALICE), vec!["1"]);
+}
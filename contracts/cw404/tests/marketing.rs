@@ -0,0 +1,120 @@
+use cosmwasm_std::{Addr, Binary, StdError};
+use cw20::{DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo, MarketingInfoResponse};
+
+use cw404::Cw404QueryMsg;
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, OWNER};
+
+const SVG: &[u8] = b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>";
+
+fn update(project: Option<&str>, description: Option<&str>, marketing: Option<&str>) -> ExecuteMsg {
+    ExecuteMsg::UpdateMarketing {
+        project: project.map(str::to_string),
+        description: description.map(str::to_string),
+        marketing: marketing.map(str::to_string),
+    }
+}
+
+fn marketing_info(suite: &Suite) -> MarketingInfoResponse {
+    suite.query(&Cw404QueryMsg::MarketingInfo {})
+}
+
+fn missing_role(address: &str) -> ContractError {
+    ContractError::MissingRole {
+        role: "marketing".to_string(),
+        address: address.to_string(),
+    }
+}
+
+#[test]
+fn marketing_updates_project_and_logo() {
+    let mut suite = Suite::new();
+
+    assert_eq!(
+        suite.execute_err(ALICE, &update(Some("Beep"), None, None)),
+        missing_role(ALICE)
+    );
+    suite
+        .execute(OWNER, &update(Some("Beep"), Some("Beep boop"), None))
+        .unwrap();
+    let info = marketing_info(&suite);
+    assert_eq!(info.project.as_deref(), Some("Beep"));
+    assert_eq!(info.description.as_deref(), Some("Beep boop"));
+    assert_eq!(info.marketing, Some(Addr::unchecked(OWNER)));
+    assert_eq!(info.logo, None);
+
+    // An empty description clears it, an unset project is kept
+    suite.execute(OWNER, &update(None, Some(""), None)).unwrap();
+    let info = marketing_info(&suite);
+    assert_eq!(info.project.as_deref(), Some("Beep"));
+    assert_eq!(info.description, None);
+
+    // Only embedded logos can be downloaded
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::UploadLogo(Logo::Url("https://beep.io/logo.png".to_string())),
+        )
+        .unwrap();
+    assert_eq!(
+        marketing_info(&suite).logo,
+        Some(LogoInfo::Url("https://beep.io/logo.png".to_string()))
+    );
+    let err = suite
+        .app
+        .wrap()
+        .query_wasm_smart::<DownloadLogoResponse>(&suite.token, &Cw404QueryMsg::DownloadLogo {})
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&StdError::not_found("logo").to_string()));
+
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Svg(Binary::from(SVG)))),
+        )
+        .unwrap();
+    assert_eq!(marketing_info(&suite).logo, Some(LogoInfo::Embedded));
+    let logo: DownloadLogoResponse = suite.query(&Cw404QueryMsg::DownloadLogo {});
+    assert_eq!(logo.mime_type, "image/svg+xml");
+    assert_eq!(logo.data, Binary::from(SVG));
+}
+
+#[test]
+fn marketing_role_is_handed_over() {
+    let mut suite = Suite::new();
+
+    suite
+        .execute(OWNER, &update(None, None, Some(ALICE)))
+        .unwrap();
+    assert_eq!(
+        marketing_info(&suite).marketing,
+        Some(Addr::unchecked(ALICE))
+    );
+    assert_eq!(
+        suite.execute_err(OWNER, &update(Some("Beep"), None, None)),
+        missing_role(OWNER)
+    );
+    assert_eq!(
+        suite.execute_err(
+            OWNER,
+            &ExecuteMsg::UploadLogo(Logo::Url("https://beep.io/logo.png".to_string())),
+        ),
+        missing_role(OWNER)
+    );
+    suite
+        .execute(ALICE, &update(Some("Beep"), None, None))
+        .unwrap();
+    assert_eq!(marketing_info(&suite).project.as_deref(), Some("Beep"));
+
+    // An empty address gives the role up
+    suite.execute(ALICE, &update(None, None, Some(""))).unwrap();
+    assert_eq!(marketing_info(&suite).marketing, None);
+    assert_eq!(
+        suite.execute_err(ALICE, &update(Some("Boop"), None, None)),
+        missing_role(ALICE)
+    );
+}
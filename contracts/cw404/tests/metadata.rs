@@ -0,0 +1,152 @@
+use cosmwasm_std::Addr;
+use cw721::NftInfoResponse;
+use cw_multi_test::Executor;
+
+use cw404::{Cw404ExecuteMsg, Cw404QueryMsg, Extension, Metadata, UserInfoResponse};
+use cw404_base::ContractError;
+
+mod common;
+use common::{Suite, ALICE, BOB, OWNER, UNIT};
+
+/// Metadata messages carry the contract's `Extension`
+fn execute(
+    suite: &mut Suite,
+    sender: &str,
+    msg: &Cw404ExecuteMsg<Extension>,
+) -> Result<(), ContractError> {
+    suite
+        .app
+        .execute_contract(Addr::unchecked(sender), suite.token.clone(), msg, &[])
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+}
+
+fn named(name: &str) -> Extension {
+    Some(Metadata {
+        name: Some(name.to_string()),
+        ..Metadata::default()
+    })
+}
+
+fn extension(suite: &Suite, token_id: &str) -> Extension {
+    let res: NftInfoResponse<Extension> = suite.query(&Cw404QueryMsg::NftInfo {
+        token_id: token_id.to_string(),
+    });
+    res.extension
+}
+
+#[test]
+fn nfts_get_their_tier_metadata() {
+    let mut suite = Suite::new();
+    for tier in 1..=5 {
+        let msg = Cw404ExecuteMsg::SetTierMetadata {
+            tier,
+            extension: named(&format!("Tier {tier}")),
+        };
+        execute(&mut suite, OWNER, &msg).unwrap();
+    }
+    for tier in [0, 6] {
+        let msg = Cw404ExecuteMsg::SetTierMetadata {
+            tier,
+            extension: None,
+        };
+        assert_eq!(
+            execute(&mut suite, OWNER, &msg),
+            Err(ContractError::InvalidTier { tier })
+        );
+    }
+
+    // Minted before the tiers had metadata
+    assert_eq!(extension(&suite, "1"), None);
+
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    let res: UserInfoResponse = suite.query(&Cw404QueryMsg::UserInfo {
+        address: BOB.to_string(),
+        start_after: None,
+        limit: None,
+    });
+    let token = &res.owned[0];
+    assert_eq!(
+        extension(&suite, &token.token_id),
+        named(&format!("Tier {}", token.tier))
+    );
+}
+
+fn token_uri(suite: &Suite, token_id: &str) -> Option<String> {
+    let res: NftInfoResponse<Extension> = suite.query(&Cw404QueryMsg::NftInfo {
+        token_id: token_id.to_string(),
+    });
+    res.token_uri
+}
+
+#[test]
+fn nfts_follow_their_tier_uri() {
+    let mut suite = Suite::new();
+    let set_uri = |id: u8| Cw404ExecuteMsg::SetBaseTokenUri {
+        id,
+        uri: format!("ipfs://tier-{id}"),
+    };
+    assert_eq!(
+        execute(&mut suite, ALICE, &set_uri(1)),
+        Err(ContractError::MissingRole {
+            role: "metadata_manager".to_string(),
+            address: ALICE.to_string(),
+        })
+    );
+    for id in 1..=5 {
+        execute(&mut suite, OWNER, &set_uri(id)).unwrap();
+    }
+
+    // Minted before the change
+    let res: cw721::AllNftInfoResponse<Extension> = suite.query(&Cw404QueryMsg::AllNftInfo {
+        token_id: "1".to_string(),
+        include_expired: None,
+    });
+    assert_eq!(token_uri(&suite, "1"), res.info.token_uri);
+    assert!(res.info.token_uri.unwrap().starts_with("ipfs://tier-"));
+
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    let res: UserInfoResponse = suite.query(&Cw404QueryMsg::UserInfo {
+        address: BOB.to_string(),
+        start_after: None,
+        limit: None,
+    });
+    let token = &res.owned[0];
+    assert_eq!(
+        token_uri(&suite, &token.token_id),
+        Some(format!("ipfs://tier-{}", token.tier))
+    );
+}
+
+#[test]
+fn metadata_manager_edits_until_frozen() {
+    let mut suite = Suite::new();
+    let update = Cw404ExecuteMsg::UpdateNftMetadata {
+        token_id: "1".to_string(),
+        extension: named("Genesis"),
+    };
+
+    assert_eq!(
+        execute(&mut suite, ALICE, &update),
+        Err(ContractError::MissingRole {
+            role: "metadata_manager".to_string(),
+            address: ALICE.to_string(),
+        })
+    );
+    execute(&mut suite, OWNER, &update).unwrap();
+    assert_eq!(extension(&suite, "1"), named("Genesis"));
+
+    execute(&mut suite, OWNER, &Cw404ExecuteMsg::FreezeMetadata {}).unwrap();
+    assert_eq!(
+        execute(&mut suite, OWNER, &update),
+        Err(ContractError::MetadataFrozen {})
+    );
+    let msg = Cw404ExecuteMsg::SetTierMetadata {
+        tier: 1,
+        extension: None,
+    };
+    assert_eq!(
+        execute(&mut suite, OWNER, &msg),
+        Err(ContractError::MetadataFrozen {})
+    );
+}
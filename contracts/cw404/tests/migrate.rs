@@ -0,0 +1,469 @@
+use cosmwasm_std::{coins, Addr, Empty, Uint128};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+use cw404::{
+    ContractInfoResponse, Cw404QueryMsg, HoldersResponse, Role, RolesResponse, StatsResponse,
+    UserInfoResponse,
+};
+use cw404_base::MigrateMsg;
+
+mod common;
+use common::{cw404_contract, ExecuteMsg, Suite, ALICE, BOB, CAROL, OWNER, PRICE, UNIT};
+
+/// Writes storage exactly as 0.1.0 left it after a short history, including
+/// what its transfer and burn paths got wrong.
+mod legacy_v0_1 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+        Storage, Uint128,
+    };
+    use cw_storage_plus::{IndexedMap, Item, Map, MultiIndex};
+
+    use cw404_base::state::{token_owner_idx, TokenIndexes, TokenInfo};
+
+    use super::{ALICE, BOB, CAROL, UNIT};
+
+    pub const TIER_URIS: [&str; 5] = [
+        "https://arweave.net/XpXSyZiPGlpcc-Dsz7XMwdxKeNuczW-01uR5rNqOj3w",
+        "https://arweave.net/FRlxtstfBtzB_ocR8l7iJhU1vHTgljm68iGefpWKs4I",
+        "https://arweave.net/er-LhktIb_jZBPwUIHX0MGSGpYMz6OAP03bk74teGRg",
+        "https://arweave.net/DpzV3S9E-7FhMKSkDAImKH8mVYgh26g5ka1rZggelr8",
+        "https://arweave.net/4vT1QhisR_8ENY9oCpz3X05qTLaMyPpkVQUSX6Ug6_I",
+    ];
+
+    /// Variations on the fixture's history
+    #[cw_serde]
+    #[derive(Default)]
+    pub struct History {
+        /// The sender removes itself from the whitelist at the end and Carol
+        /// sends it a token back, minting NFT 7 to it
+        pub owner_unwhitelisted: bool,
+        /// `minted` is missing, as on deployments that never stored it
+        pub minted_lost: bool,
+    }
+
+    #[cw_serde]
+    struct ContractInfoResponse {
+        name: String,
+        symbol: String,
+        decimals: u8,
+        total_supply: Uint128,
+    }
+
+    const OWNER: Item<String> = Item::new("owner");
+    const NAME: Item<String> = Item::new("name");
+    const SYMBOL: Item<String> = Item::new("symbol");
+    const DECIMALS: Item<u8> = Item::new("decimals");
+    const TOTAL_SUPPLY: Item<Uint128> = Item::new("total_supply");
+    const MINTED: Item<Uint128> = Item::new("minted");
+    const CONTRACT_INFO: Item<ContractInfoResponse> = Item::new("contract_info");
+    const TOKEN_URI: Map<String, String> = Map::new("token_uri");
+    const WHITELIST: Map<String, bool> = Map::new("whitelist");
+    const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
+    const OWNER_OF: Map<String, String> = Map::new("owner_of");
+    const OWNED: Map<String, Vec<Uint128>> = Map::new("owned");
+    const OWNED_INDEX: Map<String, Uint128> = Map::new("owned_index");
+
+    fn tokens<'a>() -> IndexedMap<'a, &'a str, TokenInfo<Empty>, TokenIndexes<'a, Empty>> {
+        IndexedMap::new(
+            "tokens",
+            TokenIndexes {
+                owner: MultiIndex::new(token_owner_idx, "tokens", "tokens__owner"),
+            },
+        )
+    }
+
+    /// The sender whitelists itself, sends Alice three tokens and Bob one.
+    /// Alice then moves NFT 1 to Bob, and Bob sends both his tokens to Carol.
+    ///
+    /// Alice is left owning `[3, 2]` with NFT 3 still indexed at 2, Bob with a
+    /// zero balance, and NFTs 1 and 4 burned.
+    pub fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: History,
+    ) -> StdResult<Response> {
+        // Overwritten by `CONTRACT_INFO` below, which shares the key
+        cw2::set_contract_version(deps.storage, "beepx:cw404", "0.1.0")?;
+
+        let supply = Uint128::new(100 * UNIT);
+        NAME.save(deps.storage, &"Beep".to_string())?;
+        SYMBOL.save(deps.storage, &"BEEP".to_string())?;
+        DECIMALS.save(deps.storage, &6)?;
+        TOTAL_SUPPLY.save(deps.storage, &supply)?;
+        MINTED.save(deps.storage, &Uint128::zero())?;
+        OWNER.save(deps.storage, &info.sender.to_string())?;
+        BALANCES.save(deps.storage, &info.sender, &supply)?;
+        CONTRACT_INFO.save(
+            deps.storage,
+            &ContractInfoResponse {
+                name: "Beep".to_string(),
+                symbol: "BEEP".to_string(),
+                decimals: 6,
+                total_supply: Uint128::new(100),
+            },
+        )?;
+        cw_ownable::initialize_owner(deps.storage, deps.api, Some(info.sender.as_str()))?;
+
+        let owner = info.sender.as_str();
+        WHITELIST.save(deps.storage, owner.to_string(), &true)?;
+        transfer(deps.storage, owner, ALICE, 3 * UNIT)?;
+        transfer(deps.storage, owner, BOB, UNIT)?;
+        transfer_nft(deps.storage, ALICE, BOB, 1)?;
+        transfer(deps.storage, BOB, CAROL, 2 * UNIT)?;
+        if msg.owner_unwhitelisted {
+            WHITELIST.save(deps.storage, owner.to_string(), &false)?;
+            transfer(deps.storage, CAROL, owner, UNIT)?;
+        }
+        if msg.minted_lost {
+            MINTED.remove(deps.storage);
+        }
+        Ok(Response::new())
+    }
+
+    pub fn execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Err(StdError::generic_err("not part of the fixture"))
+    }
+
+    pub fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("not part of the fixture"))
+    }
+
+    fn balance(storage: &dyn Storage, address: &str) -> StdResult<u128> {
+        Ok(BALANCES
+            .may_load(storage, &Addr::unchecked(address))?
+            .unwrap_or_default()
+            .u128())
+    }
+
+    fn whitelisted(storage: &dyn Storage, address: &str) -> StdResult<bool> {
+        Ok(WHITELIST
+            .may_load(storage, address.to_string())?
+            .unwrap_or_default())
+    }
+
+    /// Balances are saved even when they reach zero
+    fn transfer(storage: &mut dyn Storage, from: &str, to: &str, amount: u128) -> StdResult<()> {
+        let from_before = balance(storage, from)?;
+        let to_before = balance(storage, to)?;
+        BALANCES.save(
+            storage,
+            &Addr::unchecked(from),
+            &Uint128::new(from_before - amount),
+        )?;
+        BALANCES.save(
+            storage,
+            &Addr::unchecked(to),
+            &Uint128::new(to_before + amount),
+        )?;
+
+        if !whitelisted(storage, from)? {
+            for _ in 0..from_before / UNIT - (from_before - amount) / UNIT {
+                burn(storage, from)?;
+            }
+        }
+        if !whitelisted(storage, to)? {
+            for _ in 0..(to_before + amount) / UNIT - to_before / UNIT {
+                mint(storage, to)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn mint(storage: &mut dyn Storage, to: &str) -> StdResult<()> {
+        let id = MINTED.load(storage)? + Uint128::one();
+        MINTED.save(storage, &id)?;
+        OWNER_OF.save(storage, id.to_string(), &to.to_string())?;
+
+        let mut owned = OWNED.may_load(storage, to.to_string())?.unwrap_or_default();
+        owned.push(id);
+        OWNED.save(storage, to.to_string(), &owned)?;
+        OWNED_INDEX.save(
+            storage,
+            id.to_string(),
+            &Uint128::from((owned.len() - 1) as u128),
+        )?;
+
+        let token_uri = TIER_URIS[(id.u128() as usize - 1) % TIER_URIS.len()].to_string();
+        TOKEN_URI.save(storage, id.to_string(), &token_uri)?;
+        tokens().save(
+            storage,
+            &id.to_string(),
+            &TokenInfo {
+                owner: Addr::unchecked(to),
+                approvals: vec![],
+                token_uri: Some(token_uri),
+                extension: Empty {},
+            },
+        )
+    }
+
+    /// Pops the newest NFT, leaving its token URI and an empty owned list
+    fn burn(storage: &mut dyn Storage, from: &str) -> StdResult<()> {
+        let mut owned = OWNED.load(storage, from.to_string())?;
+        let id = owned.pop().unwrap();
+        OWNED.save(storage, from.to_string(), &owned)?;
+        OWNED_INDEX.remove(storage, id.to_string());
+        OWNER_OF.remove(storage, id.to_string());
+        tokens().remove(storage, &id.to_string())
+    }
+
+    /// Moves the last owned id into the freed slot without reindexing it
+    fn transfer_nft(storage: &mut dyn Storage, from: &str, to: &str, id: u128) -> StdResult<()> {
+        let id = Uint128::new(id);
+        let from_before = balance(storage, from)?;
+        let to_before = balance(storage, to)?;
+        BALANCES.save(
+            storage,
+            &Addr::unchecked(from),
+            &Uint128::new(from_before - UNIT),
+        )?;
+        BALANCES.save(
+            storage,
+            &Addr::unchecked(to),
+            &Uint128::new(to_before + UNIT),
+        )?;
+
+        OWNER_OF.save(storage, id.to_string(), &to.to_string())?;
+        let mut token = tokens().load(storage, &id.to_string())?;
+        token.owner = Addr::unchecked(to);
+        tokens().save(storage, &id.to_string(), &token)?;
+
+        let mut owned = OWNED.load(storage, from.to_string())?;
+        let last = owned[owned.len() - 1];
+        let index = OWNED_INDEX.load(storage, id.to_string())?.u128() as usize;
+        owned[index] = last;
+        owned.pop();
+        OWNED.save(storage, from.to_string(), &owned)?;
+
+        let mut to_owned = OWNED.may_load(storage, to.to_string())?.unwrap_or_default();
+        to_owned.push(id);
+        OWNED.save(storage, to.to_string(), &to_owned)?;
+        OWNED_INDEX.save(
+            storage,
+            id.to_string(),
+            &Uint128::from((to_owned.len() - 1) as u128),
+        )
+    }
+}
+
+fn legacy_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        legacy_v0_1::execute,
+        legacy_v0_1::instantiate,
+        legacy_v0_1::query,
+    ))
+}
+
+/// Instantiates the 0.1.0 fixture and migrates it to the current code
+fn migrated() -> Suite {
+    migrated_after(legacy_v0_1::History::default())
+}
+
+fn migrated_after(history: legacy_v0_1::History) -> Suite {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(BOB), coins(PRICE, "usei"))
+            .unwrap();
+    });
+    let legacy_id = app.store_code(legacy_contract());
+    let token = app
+        .instantiate_contract(
+            legacy_id,
+            Addr::unchecked(OWNER),
+            &history,
+            &[],
+            "cw404",
+            Some(OWNER.to_string()),
+        )
+        .unwrap();
+
+    let code_id = app.store_code(cw404_contract());
+    app.migrate_contract(
+        Addr::unchecked(OWNER),
+        token.clone(),
+        &MigrateMsg {},
+        code_id,
+    )
+    .unwrap();
+    Suite { app, token }
+}
+
+#[test]
+fn migrates_0_1_state() {
+    let suite = migrated();
+
+    let version = cw2::query_contract_info(&suite.app.wrap(), &suite.token).unwrap();
+    assert_eq!(version.contract, cw404_base::CONTRACT_NAME);
+    assert_eq!(version.version, cw404_base::CONTRACT_VERSION);
+
+    let res: ContractInfoResponse = suite.query(&Cw404QueryMsg::ContractInfo {});
+    assert_eq!(res.name, "Beep");
+    assert_eq!(res.total_supply, Uint128::new(100));
+
+    let res: RolesResponse = suite.query(&Cw404QueryMsg::Roles {
+        address: OWNER.to_string(),
+    });
+    assert_eq!(res.roles, Role::ALL.to_vec());
+    let ownership = suite
+        .app
+        .wrap()
+        .query_wasm_raw(&suite.token, b"ownership".to_vec())
+        .unwrap();
+    assert_eq!(ownership, None);
+
+    assert_eq!(suite.balance(OWNER), Uint128::new(96 * UNIT));
+    assert_eq!(suite.balance(ALICE), Uint128::new(2 * UNIT));
+    assert_eq!(suite.balance(CAROL), Uint128::new(2 * UNIT));
+    assert_eq!(suite.tokens(ALICE), vec!["2", "3"]);
+    assert_eq!(suite.tokens(CAROL), vec!["5", "6"]);
+
+    let res: cw721::NftInfoResponse<cw404::Extension> = suite.query(&Cw404QueryMsg::NftInfo {
+        token_id: "2".to_string(),
+    });
+    assert_eq!(res.token_uri.as_deref(), Some(legacy_v0_1::TIER_URIS[1]));
+    let token_uri = suite
+        .app
+        .wrap()
+        .query_wasm_raw(&suite.token, [b"\0\x09token_uri".as_slice(), b"2"].concat())
+        .unwrap();
+    assert_eq!(token_uri, None);
+
+    let res: StatsResponse = suite.query(&Cw404QueryMsg::Stats {});
+    assert_eq!(res.total_supply, Uint128::new(100 * UNIT));
+    assert_eq!(res.minted, Uint128::new(6));
+    assert_eq!(res.exempt_supply, Uint128::new(96 * UNIT));
+}
+
+#[test]
+fn stale_owned_index_is_rebuilt() {
+    let mut suite = migrated();
+
+    // NFT 3 was swapped into slot 0 but still indexed at 2
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::TransferNft {
+                recipient: BOB.to_string(),
+                token_id: "3".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.tokens(ALICE), vec!["2"]);
+    assert_eq!(suite.tokens(BOB), vec!["3"]);
+
+    suite.transfer(ALICE, CAROL, UNIT).unwrap();
+    assert!(suite.tokens(ALICE).is_empty());
+    assert_eq!(suite.tokens(CAROL), vec!["5", "6", "7"]);
+}
+
+fn tier_counts(suite: &Suite) -> Vec<u64> {
+    let res: StatsResponse = suite.query(&Cw404QueryMsg::Stats {});
+    res.tiers.into_iter().map(|tier| tier.count).collect()
+}
+
+#[test]
+fn tiers_and_burned_come_from_live_tokens() {
+    let mut suite = migrated();
+
+    let res: StatsResponse = suite.query(&Cw404QueryMsg::Stats {});
+    assert_eq!(res.live_nfts, Uint128::new(4));
+    assert_eq!(res.burned, Uint128::new(2));
+    let res: cw721::NumTokensResponse = suite.query(&Cw404QueryMsg::NumTokens {});
+    assert_eq!(res.count, 4);
+
+    // NFTs 1 and 4 were burned, their token URIs left behind do not count
+    assert_eq!(tier_counts(&suite), vec![1, 1, 1, 0, 1]);
+    let res: UserInfoResponse = suite.query(&Cw404QueryMsg::UserInfo {
+        address: ALICE.to_string(),
+        start_after: None,
+        limit: None,
+    });
+    let tiers: Vec<(String, u8)> = res
+        .owned
+        .into_iter()
+        .map(|token| (token.token_id, token.tier))
+        .collect();
+    assert_eq!(tiers, vec![("2".to_string(), 2), ("3".to_string(), 3)]);
+
+    // Burning NFT 6 takes it out of tier 1
+    suite.transfer(CAROL, OWNER, UNIT).unwrap();
+    assert_eq!(suite.tokens(CAROL), vec!["5"]);
+    let res: StatsResponse = suite.query(&Cw404QueryMsg::Stats {});
+    assert_eq!(res.burned, Uint128::new(3));
+    assert_eq!(tier_counts(&suite), vec![0, 1, 1, 0, 1]);
+}
+
+#[test]
+fn zero_balances_are_pruned() {
+    let suite = migrated();
+
+    // Bob sent everything he had to Carol
+    let res: HoldersResponse = suite.query(&Cw404QueryMsg::Holders {
+        start_after: None,
+        limit: None,
+    });
+    let holders: Vec<String> = res
+        .holders
+        .into_iter()
+        .map(|holder| holder.address)
+        .collect();
+    assert_eq!(holders, vec![ALICE, CAROL, OWNER]);
+    assert_eq!(res.holder_count, 3);
+    assert_eq!(res.nft_holder_count, 2);
+
+    let res: cw20::AllAccountsResponse = suite.query(&Cw404QueryMsg::AllAccounts {
+        start_after: None,
+        limit: None,
+    });
+    assert_eq!(res.accounts, vec![ALICE, CAROL, OWNER]);
+}
+
+#[test]
+fn unwhitelisted_inventory_is_whitelisted() {
+    let mut suite = migrated_after(legacy_v0_1::History {
+        owner_unwhitelisted: true,
+        ..Default::default()
+    });
+
+    // NFT 7 backed part of the unsold supply
+    assert!(suite.tokens(OWNER).is_empty());
+    let res: StatsResponse = suite.query(&Cw404QueryMsg::Stats {});
+    assert_eq!(res.minted, Uint128::new(7));
+    assert_eq!(res.burned, Uint128::new(4));
+    assert_eq!(res.live_nfts, Uint128::new(3));
+    assert_eq!(res.exempt_supply, Uint128::new(97 * UNIT));
+    let res: HoldersResponse = suite.query(&Cw404QueryMsg::Holders {
+        start_after: None,
+        limit: None,
+    });
+    assert_eq!(res.nft_holder_count, 2);
+
+    // Sales leave the inventory without burning
+    suite.native_mint(PRICE).unwrap();
+    assert_eq!(suite.balance(OWNER), Uint128::new(96 * UNIT));
+    assert_eq!(suite.tokens(BOB), vec!["8"]);
+}
+
+#[test]
+fn lost_minted_is_recovered_from_live_ids() {
+    let mut suite = migrated_after(legacy_v0_1::History {
+        minted_lost: true,
+        ..Default::default()
+    });
+
+    let res: StatsResponse = suite.query(&Cw404QueryMsg::Stats {});
+    assert_eq!(res.minted, Uint128::new(6));
+    assert_eq!(res.burned, Uint128::new(2));
+
+    suite.transfer(OWNER, BOB, UNIT).unwrap();
+    assert_eq!(suite.tokens(BOB), vec!["7"]);
+}
@@ -0,0 +1,116 @@
+use cw404::{Cw404QueryMsg, PauseStatusResponse, PauseTarget, PausedTarget};
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, OWNER, PRICE, UNIT};
+
+fn pause(targets: Vec<PauseTarget>, until_height: Option<u64>) -> ExecuteMsg {
+    ExecuteMsg::Pause {
+        targets,
+        until_height,
+    }
+}
+
+fn transfer_nft(token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::TransferNft {
+        recipient: BOB.to_string(),
+        token_id: token_id.to_string(),
+    }
+}
+
+fn paused(target: PauseTarget) -> ContractError {
+    ContractError::Paused {
+        target: target.to_string(),
+    }
+}
+
+#[test]
+fn pause_gates_only_its_targets() {
+    let mut suite = Suite::new();
+
+    assert_eq!(
+        suite.execute_err(ALICE, &pause(vec![], None)),
+        ContractError::MissingRole {
+            role: "pauser".to_string(),
+            address: ALICE.to_string(),
+        }
+    );
+
+    suite
+        .execute(OWNER, &pause(vec![PauseTarget::NftTransfers], None))
+        .unwrap();
+    assert_eq!(
+        suite.execute_err(ALICE, &transfer_nft("1")),
+        paused(PauseTarget::NftTransfers)
+    );
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    suite.native_mint(PRICE).unwrap();
+
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::Unpause {
+                targets: vec![PauseTarget::NftTransfers],
+            },
+        )
+        .unwrap();
+    suite.execute(ALICE, &transfer_nft("2")).unwrap();
+
+    // No targets pauses everything
+    suite.execute(OWNER, &pause(vec![], None)).unwrap();
+    let res: PauseStatusResponse = suite.query(&Cw404QueryMsg::PauseStatus {});
+    let targets: Vec<PauseTarget> = res.paused.into_iter().map(|p| p.target).collect();
+    assert_eq!(targets, PauseTarget::ALL.to_vec());
+    assert_eq!(
+        suite
+            .transfer(BOB, ALICE, UNIT)
+            .unwrap_err()
+            .downcast::<ContractError>()
+            .unwrap(),
+        paused(PauseTarget::FungibleTransfers)
+    );
+}
+
+#[test]
+fn pause_lifts_at_height() {
+    let mut suite = Suite::new();
+    let height = suite.app.block_info().height;
+
+    suite
+        .execute(
+            OWNER,
+            &pause(vec![PauseTarget::FungibleTransfers], Some(height + 2)),
+        )
+        .unwrap();
+    let res: PauseStatusResponse = suite.query(&Cw404QueryMsg::PauseStatus {});
+    assert_eq!(
+        res.paused,
+        vec![PausedTarget {
+            target: PauseTarget::FungibleTransfers,
+            until_height: Some(height + 2),
+        }]
+    );
+
+    suite.app.update_block(|block| block.height += 1);
+    suite.transfer(ALICE, BOB, UNIT).unwrap_err();
+
+    suite.app.update_block(|block| block.height += 1);
+    suite.transfer(ALICE, BOB, UNIT).unwrap();
+    let res: PauseStatusResponse = suite.query(&Cw404QueryMsg::PauseStatus {});
+    assert!(res.paused.is_empty());
+}
+
+#[test]
+fn pause_must_end_in_the_future() {
+    let mut suite = Suite::new();
+    let height = suite.app.block_info().height;
+
+    assert_eq!(
+        suite.execute_err(OWNER, &pause(vec![], Some(height))),
+        ContractError::InvalidPauseHeight { height }
+    );
+    assert_eq!(
+        suite.execute_err(OWNER, &pause(vec![], Some(height - 1))),
+        ContractError::InvalidPauseHeight { height: height - 1 }
+    );
+}
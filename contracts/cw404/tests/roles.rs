@@ -0,0 +1,122 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_multi_test::Executor;
+
+use cw404::{Cw404QueryMsg, Role, RoleMembersResponse, RolesResponse};
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, OWNER};
+
+fn roles(suite: &Suite, address: &str) -> Vec<Role> {
+    let res: RolesResponse = suite.query(&Cw404QueryMsg::Roles {
+        address: address.to_string(),
+    });
+    res.roles
+}
+
+fn grant(role: Role, address: &str) -> ExecuteMsg {
+    ExecuteMsg::GrantRole {
+        role,
+        address: address.to_string(),
+    }
+}
+
+fn revoke(role: Role, address: &str) -> ExecuteMsg {
+    ExecuteMsg::RevokeRole {
+        role,
+        address: address.to_string(),
+    }
+}
+
+fn missing_role(role: Role, address: &str) -> ContractError {
+    ContractError::MissingRole {
+        role: role.to_string(),
+        address: address.to_string(),
+    }
+}
+
+#[test]
+fn admin_grants_and_revokes_roles() {
+    let mut suite = Suite::new();
+    assert_eq!(roles(&suite, OWNER), Role::ALL.to_vec());
+
+    let whitelist = ExecuteMsg::SetWhitelist {
+        target: BOB.to_string(),
+        state: true,
+    };
+    assert_eq!(
+        suite.execute_err(ALICE, &whitelist),
+        missing_role(Role::WhitelistManager, ALICE)
+    );
+
+    suite
+        .execute(OWNER, &grant(Role::WhitelistManager, ALICE))
+        .unwrap();
+    assert_eq!(roles(&suite, ALICE), vec![Role::WhitelistManager]);
+    let res: RoleMembersResponse = suite.query(&Cw404QueryMsg::RoleMembers {
+        role: Role::WhitelistManager,
+        start_after: None,
+        limit: None,
+    });
+    assert_eq!(res.members, vec![ALICE, OWNER]);
+    suite.execute(ALICE, &whitelist).unwrap();
+
+    // Only admins hand out roles
+    assert_eq!(
+        suite.execute_err(ALICE, &grant(Role::Minter, BOB)),
+        missing_role(Role::Admin, ALICE)
+    );
+
+    suite
+        .execute(OWNER, &revoke(Role::WhitelistManager, ALICE))
+        .unwrap();
+    assert!(roles(&suite, ALICE).is_empty());
+    assert_eq!(
+        suite.execute_err(ALICE, &whitelist),
+        missing_role(Role::WhitelistManager, ALICE)
+    );
+    assert_eq!(
+        suite.execute_err(OWNER, &revoke(Role::WhitelistManager, ALICE)),
+        missing_role(Role::WhitelistManager, ALICE)
+    );
+}
+
+#[test]
+fn last_admin_cannot_be_revoked() {
+    let mut suite = Suite::new();
+
+    assert_eq!(
+        suite.execute_err(OWNER, &revoke(Role::Admin, OWNER)),
+        ContractError::LastAdmin {}
+    );
+
+    suite.execute(OWNER, &grant(Role::Admin, BOB)).unwrap();
+    suite.execute(BOB, &revoke(Role::Admin, OWNER)).unwrap();
+    assert_eq!(
+        suite.execute_err(OWNER, &grant(Role::Admin, OWNER)),
+        missing_role(Role::Admin, OWNER)
+    );
+    assert_eq!(
+        suite.execute_err(BOB, &revoke(Role::Admin, BOB)),
+        ContractError::LastAdmin {}
+    );
+}
+
+/// What `cw_ownable` used to add to the execute message
+#[cw_serde]
+enum OwnableExecuteMsg {
+    UpdateOwnership(cw_ownable::Action),
+}
+
+#[test]
+fn ownership_messages_are_gone() {
+    let mut suite = Suite::new();
+
+    let msg = OwnableExecuteMsg::UpdateOwnership(cw_ownable::Action::RenounceOwnership);
+    suite
+        .app
+        .execute_contract(Addr::unchecked(OWNER), suite.token.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(roles(&suite, OWNER), Role::ALL.to_vec());
+}
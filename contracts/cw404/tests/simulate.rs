@@ -0,0 +1,111 @@
+use cosmwasm_std::{Event, Uint128};
+use cw_multi_test::AppResponse;
+
+use cw404::{Cw404QueryMsg, FeeConfig, SimulateTransferResponse};
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, CAROL, OWNER, UNIT};
+
+const TREASURY: &str = "treasury";
+
+/// 1% transfer fee
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetFeeConfig {
+                config: Some(FeeConfig {
+                    recipient: TREASURY.to_string(),
+                    transfer_bps: 100,
+                    buy_bps: 0,
+                    sell_bps: 0,
+                }),
+            },
+        )
+        .unwrap();
+    suite
+}
+
+/// `token_id`s of the `kind` events of `res` where `key` is `address`
+fn token_ids(res: &AppResponse, kind: &str, key: &str, address: &str) -> Vec<String> {
+    let attr = |event: &Event, key: &str| {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.clone())
+    };
+    res.events
+        .iter()
+        .filter(|event| event.ty == format!("wasm-{kind}"))
+        .filter(|event| attr(event, key).as_deref() == Some(address))
+        .filter_map(|event| attr(event, "token_id"))
+        .collect()
+}
+
+/// Simulates, then executes, a transfer and checks the preview matches what
+/// the transfer did.
+fn assert_parity(
+    suite: &mut Suite,
+    from: &str,
+    to: &str,
+    amount: u128,
+) -> SimulateTransferResponse {
+    let simulated: SimulateTransferResponse = suite.query(&Cw404QueryMsg::SimulateTransfer {
+        from: from.to_string(),
+        to: to.to_string(),
+        amount: Uint128::new(amount),
+    });
+    let treasury_before = suite.balance(TREASURY);
+    let to_before = suite.balance(to);
+
+    let res = match suite.transfer(from, to, amount) {
+        Ok(res) => res,
+        Err(err) => {
+            let err = err.downcast::<ContractError>().unwrap();
+            assert_eq!(simulated.error, Some(err.to_string()));
+            return simulated;
+        }
+    };
+    assert_eq!(simulated.error, None);
+    assert_eq!(simulated.fee, suite.balance(TREASURY) - treasury_before);
+    assert_eq!(simulated.received, suite.balance(to) - to_before);
+    assert_eq!(
+        simulated.burned_ids,
+        token_ids(&res, "cw721_burn", "from", from)
+    );
+    assert_eq!(
+        simulated.minted as usize,
+        token_ids(&res, "cw721_mint", "to", to).len()
+    );
+    simulated
+}
+
+#[test]
+fn simulation_matches_the_transfer() {
+    let mut suite = setup();
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::SetBurnOrder {
+                token_ids: vec!["1".to_string()],
+            },
+        )
+        .unwrap();
+
+    let res = assert_parity(&mut suite, ALICE, BOB, 3 * UNIT / 2);
+    assert_eq!(res.burned_ids, vec!["1", "2"]);
+    assert_eq!(res.minted, 1);
+    assert_eq!(res.fee, Uint128::new(15_000));
+
+    let res = assert_parity(&mut suite, BOB, CAROL, UNIT / 2);
+    assert_eq!(res.burned_ids, vec!["4"]);
+    assert_eq!(res.minted, 0);
+    assert_eq!(res.received, Uint128::new(495_000));
+
+    let res = assert_parity(&mut suite, ALICE, CAROL, 10 * UNIT);
+    assert!(res.error.is_some());
+    assert!(res.burned_ids.is_empty());
+}
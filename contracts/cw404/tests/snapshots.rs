@@ -0,0 +1,49 @@
+use cosmwasm_std::Uint128;
+use cw20::BalanceResponse;
+
+use cw404::{Cw404QueryMsg, NftCountResponse, TotalSupplyResponse};
+
+mod common;
+use common::{Suite, ALICE, BOB, UNIT};
+
+fn balance_at(suite: &Suite, address: &str, height: u64) -> Uint128 {
+    let res: BalanceResponse = suite.query(&Cw404QueryMsg::BalanceAtHeight {
+        address: address.to_string(),
+        height,
+    });
+    res.balance
+}
+
+fn nft_count_at(suite: &Suite, address: &str, height: u64) -> u32 {
+    let res: NftCountResponse = suite.query(&Cw404QueryMsg::NftCountAtHeight {
+        address: address.to_string(),
+        height,
+    });
+    res.count
+}
+
+#[test]
+fn values_at_past_heights() {
+    let mut suite = Suite::new();
+    let start = suite.app.block_info().height;
+
+    suite.app.update_block(|block| block.height += 1);
+    suite.transfer(ALICE, BOB, 2 * UNIT).unwrap();
+    suite.app.update_block(|block| block.height += 1);
+
+    // Heights report the state before any transaction of that block
+    assert_eq!(balance_at(&suite, ALICE, start), Uint128::zero());
+    assert_eq!(balance_at(&suite, ALICE, start + 1), Uint128::new(3 * UNIT));
+    assert_eq!(balance_at(&suite, ALICE, start + 2), Uint128::new(UNIT));
+    assert_eq!(balance_at(&suite, BOB, start + 1), Uint128::zero());
+    assert_eq!(balance_at(&suite, BOB, start + 2), Uint128::new(2 * UNIT));
+
+    assert_eq!(nft_count_at(&suite, ALICE, start + 1), 3);
+    assert_eq!(nft_count_at(&suite, ALICE, start + 2), 1);
+    assert_eq!(nft_count_at(&suite, BOB, start + 1), 0);
+    assert_eq!(nft_count_at(&suite, BOB, start + 2), 2);
+
+    let res: TotalSupplyResponse =
+        suite.query(&Cw404QueryMsg::TotalSupplyAtHeight { height: start + 1 });
+    assert_eq!(res.total_supply, Uint128::new(100 * UNIT));
+}
@@ -0,0 +1,80 @@
+use cosmwasm_std::Uint128;
+
+use cw404::{Cw404QueryMsg, StatsResponse, TierCount, UserInfoResponse};
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, CAROL, OWNER, PRICE, UNIT};
+
+fn stats(suite: &Suite) -> StatsResponse {
+    suite.query(&Cw404QueryMsg::Stats {})
+}
+
+/// Live NFTs per tier, counted from what each of `owners` holds
+fn owned_tiers(suite: &Suite, owners: &[&str]) -> Vec<TierCount> {
+    let mut tiers: Vec<_> = (1..=5).map(|tier| TierCount { tier, count: 0 }).collect();
+    for owner in owners {
+        let res: UserInfoResponse = suite.query(&Cw404QueryMsg::UserInfo {
+            address: owner.to_string(),
+            start_after: None,
+            limit: None,
+        });
+        for token in res.owned {
+            tiers[usize::from(token.tier - 1)].count += 1;
+        }
+    }
+    tiers
+}
+
+fn whitelist(suite: &mut Suite, target: &str, state: bool) {
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetWhitelist {
+                target: target.to_string(),
+                state,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn stats_track_supply_and_tiers() {
+    let mut suite = Suite::new();
+
+    let res = stats(&suite);
+    assert_eq!(res.total_supply, Uint128::new(100 * UNIT));
+    assert_eq!(res.total_native_supply, Uint128::new(100));
+    assert_eq!(res.circulating_supply, Uint128::new(3 * UNIT));
+    assert_eq!(res.exempt_supply, Uint128::new(97 * UNIT));
+    assert_eq!(res.live_nfts, Uint128::new(3));
+    assert_eq!((res.minted, res.burned), (Uint128::new(3), Uint128::zero()));
+    assert_eq!(res.tiers, owned_tiers(&suite, &[ALICE]));
+
+    // Whitelisting burns Bob's NFTs, removing it mints them again
+    suite.transfer(OWNER, BOB, 2 * UNIT).unwrap();
+    whitelist(&mut suite, BOB, true);
+    let res = stats(&suite);
+    assert_eq!(res.circulating_supply, Uint128::new(5 * UNIT));
+    assert_eq!(res.exempt_supply, Uint128::new(97 * UNIT));
+    assert_eq!(res.live_nfts, Uint128::new(3));
+    assert_eq!((res.minted, res.burned), (Uint128::new(5), Uint128::new(2)));
+    assert_eq!(res.tiers, owned_tiers(&suite, &[ALICE]));
+
+    whitelist(&mut suite, BOB, false);
+    let res = stats(&suite);
+    assert_eq!(res.exempt_supply, Uint128::new(95 * UNIT));
+    assert_eq!(res.live_nfts, Uint128::new(5));
+    assert_eq!((res.minted, res.burned), (Uint128::new(7), Uint128::new(2)));
+    assert_eq!(res.tiers, owned_tiers(&suite, &[ALICE, BOB]));
+
+    // Alice drops to one and a half tokens, burning two NFTs, and Bob buys one
+    suite.transfer(ALICE, CAROL, 3 * UNIT / 2).unwrap();
+    suite.native_mint(PRICE).unwrap();
+    let res = stats(&suite);
+    assert_eq!(res.total_supply, Uint128::new(100 * UNIT));
+    assert_eq!(res.circulating_supply, Uint128::new(6 * UNIT));
+    assert_eq!(res.exempt_supply, Uint128::new(94 * UNIT));
+    assert_eq!(res.live_nfts, Uint128::new(5));
+    assert_eq!((res.minted, res.burned), (Uint128::new(9), Uint128::new(4)));
+    assert_eq!(res.tiers, owned_tiers(&suite, &[ALICE, BOB, CAROL]));
+}
@@ -0,0 +1,85 @@
+use cosmwasm_std::{from_json, Addr, Binary, WasmMsg};
+use cw_multi_test::{AppResponse, Executor};
+
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB};
+
+/// Executes `json` as Alice, exactly as an integrator would send it
+fn execute_json(suite: &mut Suite, json: &str) -> anyhow::Result<AppResponse> {
+    suite.app.execute(
+        Addr::unchecked(ALICE),
+        WasmMsg::Execute {
+            contract_addr: suite.token.to_string(),
+            msg: Binary::from(json.as_bytes()),
+            funds: vec![],
+        }
+        .into(),
+    )
+}
+
+#[test]
+fn string_and_numeric_ids_deserialize_alike() {
+    let string: ExecuteMsg =
+        from_json(br#"{"transfer_nft":{"recipient":"bob","token_id":"12"}}"#).unwrap();
+    let numeric: ExecuteMsg =
+        from_json(br#"{"transfer_nft":{"recipient":"bob","token_id":12}}"#).unwrap();
+    assert_eq!(string, numeric);
+    assert_eq!(
+        string,
+        ExecuteMsg::TransferNft {
+            recipient: BOB.to_string(),
+            token_id: "12".to_string(),
+        }
+    );
+
+    for json in [
+        r#"{"approve":{"spender":"bob","token_id":-1}}"#,
+        r#"{"approve":{"spender":"bob","token_id":1.5}}"#,
+        r#"{"approve":{"spender":"bob","token_id":null}}"#,
+    ] {
+        from_json::<ExecuteMsg>(json.as_bytes()).unwrap_err();
+    }
+}
+
+#[test]
+fn cw721_and_legacy_messages_transfer_nfts() {
+    let mut suite = Suite::new();
+
+    execute_json(
+        &mut suite,
+        r#"{"transfer_nft":{"recipient":"bob","token_id":"1"}}"#,
+    )
+    .unwrap();
+    execute_json(
+        &mut suite,
+        r#"{"transfer_nft":{"recipient":"bob","token_id":2}}"#,
+    )
+    .unwrap();
+    assert_eq!(suite.tokens(BOB), vec!["1", "2"]);
+}
+
+#[test]
+fn invalid_ids_are_rejected() {
+    let mut suite = Suite::new();
+    let transfer = |token_id: &str| ExecuteMsg::TransferNft {
+        recipient: BOB.to_string(),
+        token_id: token_id.to_string(),
+    };
+
+    for token_id in ["abc", "0", "", "1.0"] {
+        assert_eq!(
+            suite.execute_err(ALICE, &transfer(token_id)),
+            ContractError::InvalidTokenId {
+                token_id: token_id.to_string(),
+            }
+        );
+    }
+    assert_eq!(
+        suite.execute_err(ALICE, &transfer("99")),
+        ContractError::TokenNotFound {
+            token_id: "99".to_string(),
+        }
+    );
+}
@@ -0,0 +1,60 @@
+use cosmwasm_std::Uint128;
+
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, OWNER, UNIT};
+
+fn transfer_with_ids(amount: u128, token_ids: &[&str]) -> ExecuteMsg {
+    ExecuteMsg::TransferWithIds {
+        recipient: BOB.to_string(),
+        amount: Uint128::new(amount),
+        token_ids: token_ids.iter().map(|id| id.to_string()).collect(),
+    }
+}
+
+#[test]
+fn listed_nfts_move_with_the_remainder() {
+    let mut suite = Suite::new();
+
+    suite
+        .execute(ALICE, &transfer_with_ids(3 * UNIT / 2, &["2"]))
+        .unwrap();
+    assert_eq!(suite.tokens(BOB), vec!["2"]);
+    assert_eq!(suite.balance(BOB), Uint128::new(3 * UNIT / 2));
+    // The half unit left Alice short of NFT 3, her newest
+    assert_eq!(suite.tokens(ALICE), vec!["1"]);
+    assert_eq!(suite.balance(ALICE), Uint128::new(3 * UNIT / 2));
+}
+
+#[test]
+fn unowned_or_unknown_ids_fail_the_whole_transfer() {
+    let mut suite = Suite::new();
+    suite.transfer(OWNER, BOB, UNIT).unwrap();
+
+    assert_eq!(
+        suite.execute_err(ALICE, &transfer_with_ids(3 * UNIT / 2, &["1", "2"])),
+        ContractError::AmountBelowTokenIds {
+            amount: Uint128::new(3 * UNIT / 2),
+            required: Uint128::new(2 * UNIT),
+        }
+    );
+    assert_eq!(
+        suite.execute_err(ALICE, &transfer_with_ids(2 * UNIT, &["1", "4"])),
+        ContractError::NotTokenOwner {
+            token_id: "4".to_string(),
+            address: ALICE.to_string(),
+        }
+    );
+    assert_eq!(
+        suite.execute_err(ALICE, &transfer_with_ids(2 * UNIT, &["1", "99"])),
+        ContractError::TokenNotFound {
+            token_id: "99".to_string(),
+        }
+    );
+
+    // NFT 1 moved before each failing id, and is back with Alice
+    assert_eq!(suite.tokens(ALICE), vec!["1", "2", "3"]);
+    assert_eq!(suite.tokens(BOB), vec!["4"]);
+    assert_eq!(suite.balance(ALICE), Uint128::new(3 * UNIT));
+}
@@ -0,0 +1,72 @@
+use cosmwasm_std::Uint128;
+
+use cw404::{Cw404QueryMsg, UserInfoResponse};
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, CAROL, OWNER, UNIT};
+
+fn user_info(suite: &Suite, start_after: Option<&str>, limit: Option<u32>) -> UserInfoResponse {
+    suite.query(&Cw404QueryMsg::UserInfo {
+        address: ALICE.to_string(),
+        start_after: start_after.map(str::to_string),
+        limit,
+    })
+}
+
+fn page(suite: &Suite, start_after: Option<&str>, limit: Option<u32>) -> Vec<String> {
+    user_info(suite, start_after, limit)
+        .owned
+        .into_iter()
+        .map(|token| token.token_id)
+        .collect()
+}
+
+#[test]
+fn owned_nfts_page_by_numeric_id() {
+    let mut suite = Suite::new();
+    // Alice ends up with NFTs 1 to 12
+    suite.transfer(OWNER, ALICE, 9 * UNIT).unwrap();
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::Lock {
+                token_id: "11".to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        page(&suite, None, None),
+        (1..=10).map(|id| id.to_string()).collect::<Vec<_>>()
+    );
+    assert_eq!(page(&suite, None, Some(3)), vec!["1", "2", "3"]);
+    assert_eq!(page(&suite, Some("9"), Some(2)), vec!["10", "11"]);
+    assert_eq!(page(&suite, Some("11"), None), vec!["12"]);
+    assert!(page(&suite, Some("12"), None).is_empty());
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart::<UserInfoResponse>(
+            &suite.token,
+            &Cw404QueryMsg::UserInfo {
+                address: ALICE.to_string(),
+                start_after: Some("abc".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap_err();
+
+    // `locked` follows the page, the rest covers the whole account
+    let info = user_info(&suite, None, Some(1));
+    assert_eq!(info.nft_count, 12);
+    assert!(info.locked.is_empty());
+    assert_eq!(user_info(&suite, Some("9"), Some(2)).locked, vec!["11"]);
+    assert_eq!(user_info(&suite, None, Some(0)).owned, vec![]);
+
+    suite.transfer(ALICE, CAROL, UNIT / 4).unwrap();
+    let info = user_info(&suite, None, None);
+    assert_eq!(info.balance, Uint128::new(11 * UNIT + 3 * UNIT / 4));
+    assert_eq!(info.whole_units, Uint128::new(11));
+    assert_eq!(info.fractional, Uint128::new(3 * UNIT / 4));
+    assert_eq!(info.nft_count, 11);
+}
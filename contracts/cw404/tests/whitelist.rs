@@ -0,0 +1,45 @@
+use cosmwasm_std::Uint128;
+
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, BOB, OWNER, UNIT};
+
+fn set_whitelist(target: &str, state: bool) -> ExecuteMsg {
+    ExecuteMsg::SetWhitelist {
+        target: target.to_string(),
+        state,
+    }
+}
+
+#[test]
+fn whitelisting_burns_and_unwhitelisting_mints_nfts() {
+    let mut suite = Suite::new();
+
+    suite.execute(OWNER, &set_whitelist(ALICE, true)).unwrap();
+    assert!(suite.tokens(ALICE).is_empty());
+    suite.transfer(OWNER, ALICE, UNIT / 2).unwrap();
+    assert!(suite.tokens(ALICE).is_empty());
+
+    // Three and a half tokens back three NFTs once Alice is no longer exempt
+    suite.execute(OWNER, &set_whitelist(ALICE, false)).unwrap();
+    assert_eq!(suite.tokens(ALICE), vec!["4", "5", "6"]);
+
+    suite.transfer(ALICE, BOB, 2 * UNIT).unwrap();
+    assert_eq!(suite.tokens(ALICE), vec!["4"]);
+    assert_eq!(suite.balance(ALICE), Uint128::new(3 * UNIT / 2));
+    assert_eq!(suite.tokens(BOB), vec!["7", "8"]);
+}
+
+#[test]
+fn sale_inventory_stays_whitelisted() {
+    let mut suite = Suite::new();
+
+    assert_eq!(
+        suite.execute_err(OWNER, &set_whitelist(OWNER, false)),
+        ContractError::SaleInventoryWhitelist {
+            address: OWNER.to_string(),
+        }
+    );
+    assert!(suite.tokens(OWNER).is_empty());
+}
@@ -0,0 +1,100 @@
+use cosmwasm_std::{coin, coins, Coin};
+
+use cw404::Payout;
+use cw404_base::ContractError;
+
+mod common;
+use common::{ExecuteMsg, Suite, ALICE, CAROL, OWNER, PRICE};
+
+const TREASURY: &str = "treasury";
+
+fn fund(suite: &mut Suite, funds: Vec<Coin>) {
+    let token = suite.token.clone();
+    suite
+        .app
+        .init_modules(|router, _, storage| router.bank.init_balance(storage, &token, funds))
+        .unwrap();
+}
+
+fn balances(suite: &Suite, address: &str) -> Vec<Coin> {
+    suite.app.wrap().query_all_balances(address).unwrap()
+}
+
+fn payout(address: &str, bps: u16) -> Payout {
+    Payout {
+        address: address.to_string(),
+        bps,
+    }
+}
+
+#[test]
+fn withdrawals_need_a_treasurer_and_a_destination() {
+    let mut suite = Suite::new();
+    let withdraw = ExecuteMsg::WithdrawFunds {
+        amount: coin(PRICE, "usei"),
+    };
+
+    assert_eq!(
+        suite.execute_err(ALICE, &withdraw),
+        ContractError::MissingRole {
+            role: "treasurer".to_string(),
+            address: ALICE.to_string(),
+        }
+    );
+    assert_eq!(
+        suite.execute_err(OWNER, &ExecuteMsg::WithdrawAll {}),
+        ContractError::NoFunds {}
+    );
+
+    suite.native_mint(PRICE).unwrap();
+    assert_eq!(
+        suite.execute_err(OWNER, &withdraw),
+        ContractError::NoWithdrawAddress {}
+    );
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetWithdrawAddress {
+                address: TREASURY.to_string(),
+            },
+        )
+        .unwrap();
+    suite.execute(OWNER, &withdraw).unwrap();
+    assert_eq!(balances(&suite, TREASURY), coins(PRICE, "usei"));
+}
+
+#[test]
+fn withdraw_all_splits_every_denom() {
+    let mut suite = Suite::new();
+    fund(&mut suite, vec![coin(10, "uatom"), coin(1_001, "usei")]);
+
+    assert_eq!(
+        suite.execute_err(
+            OWNER,
+            &ExecuteMsg::SetPayouts {
+                payouts: vec![payout(CAROL, 3_000), payout(TREASURY, 6_000)],
+            },
+        ),
+        ContractError::InvalidPayouts { total_bps: 9_000 }
+    );
+    suite
+        .execute(
+            OWNER,
+            &ExecuteMsg::SetPayouts {
+                payouts: vec![payout(CAROL, 3_000), payout(TREASURY, 7_000)],
+            },
+        )
+        .unwrap();
+
+    // Rounding dust goes to the last payout
+    suite.execute(OWNER, &ExecuteMsg::WithdrawAll {}).unwrap();
+    assert_eq!(
+        balances(&suite, CAROL),
+        vec![coin(3, "uatom"), coin(300, "usei")]
+    );
+    assert_eq!(
+        balances(&suite, TREASURY),
+        vec![coin(7, "uatom"), coin(701, "usei")]
+    );
+    assert!(balances(&suite, suite.token.as_str()).is_empty());
+}
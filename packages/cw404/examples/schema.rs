@@ -0,0 +1,48 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
+
+use cw404::{
+    AddressesResponse, BurnOrderResponse, ContractInfoResponse, Cw404ExecuteMsg,
+    Cw404InstantiateMsg, Cw404MigrateMsg, Cw404QueryMsg, Extension, FeeConfigResponse,
+    HoldersResponse, HooksResponse, IsLockedResponse, LaunchStatusResponse,
+    MetadataManagerResponse, MinterResponse, NftCountResponse, PauseStatusResponse,
+    PayoutsResponse, RoleMembersResponse, RolesResponse, SimulateTransferResponse, StatsResponse,
+    TotalSupplyResponse, UserInfoResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(Cw404InstantiateMsg), &out_dir);
+    export_schema_with_title(
+        &schema_for!(Cw404ExecuteMsg<Extension>),
+        &out_dir,
+        "Cw404ExecuteMsg",
+    );
+    export_schema(&schema_for!(Cw404QueryMsg), &out_dir);
+    export_schema(&schema_for!(Cw404MigrateMsg), &out_dir);
+    export_schema(&schema_for!(AddressesResponse), &out_dir);
+    export_schema(&schema_for!(BurnOrderResponse), &out_dir);
+    export_schema(&schema_for!(ContractInfoResponse), &out_dir);
+    export_schema(&schema_for!(FeeConfigResponse), &out_dir);
+    export_schema(&schema_for!(HoldersResponse), &out_dir);
+    export_schema(&schema_for!(HooksResponse), &out_dir);
+    export_schema(&schema_for!(IsLockedResponse), &out_dir);
+    export_schema(&schema_for!(LaunchStatusResponse), &out_dir);
+    export_schema(&schema_for!(MetadataManagerResponse), &out_dir);
+    export_schema(&schema_for!(MinterResponse), &out_dir);
+    export_schema(&schema_for!(NftCountResponse), &out_dir);
+    export_schema(&schema_for!(PauseStatusResponse), &out_dir);
+    export_schema(&schema_for!(PayoutsResponse), &out_dir);
+    export_schema(&schema_for!(RoleMembersResponse), &out_dir);
+    export_schema(&schema_for!(RolesResponse), &out_dir);
+    export_schema(&schema_for!(SimulateTransferResponse), &out_dir);
+    export_schema(&schema_for!(StatsResponse), &out_dir);
+    export_schema(&schema_for!(TotalSupplyResponse), &out_dir);
+    export_schema(&schema_for!(UserInfoResponse), &out_dir);
+}
@@ -0,0 +1,148 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, CustomQuery, Empty, QuerierWrapper, QueryRequest,
+    StdResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::BalanceResponse;
+use cw721::{OwnerOfResponse, TokensResponse};
+use serde::Serialize;
+
+use crate::{Cw404ExecuteMsg, Cw404QueryMsg};
+
+/// Cw404Contract is a wrapper around Addr that provides a lot of helpers
+/// for working with a cw404 token from another contract.
+#[cw_serde]
+pub struct Cw404Contract(pub Addr);
+
+impl Cw404Contract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    pub fn call<T: Serialize>(&self, msg: Cw404ExecuteMsg<T>) -> StdResult<CosmosMsg> {
+        let msg = to_json_binary(&msg)?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    fn encode_smart_query<CQ: CustomQuery>(
+        &self,
+        msg: Cw404QueryMsg,
+    ) -> StdResult<QueryRequest<CQ>> {
+        Ok(WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&msg)?,
+        }
+        .into())
+    }
+
+    /// Get the fungible balance, in base units, of the given address
+    pub fn balance<T, CQ>(&self, querier: &QuerierWrapper<CQ>, address: T) -> StdResult<Uint128>
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        let query = self.encode_smart_query(Cw404QueryMsg::Balance {
+            address: address.into(),
+        })?;
+        let res: BalanceResponse = querier.query(&query)?;
+        Ok(res.balance)
+    }
+
+    /// Get the owner of the given NFT
+    pub fn owner_of<T, CQ>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        token_id: T,
+        include_expired: bool,
+    ) -> StdResult<OwnerOfResponse>
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        let query = self.encode_smart_query(Cw404QueryMsg::OwnerOf {
+            token_id: token_id.into(),
+            include_expired: Some(include_expired),
+        })?;
+        querier.query(&query)
+    }
+
+    /// List the NFTs held by `owner`
+    pub fn tokens<T, CQ>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        owner: T,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse>
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        let query = self.encode_smart_query(Cw404QueryMsg::Tokens {
+            owner: owner.into(),
+            start_after,
+            limit,
+        })?;
+        querier.query(&query)
+    }
+
+    /// Build a fungible transfer of `amount` base units
+    pub fn transfer<T: Into<String>>(&self, recipient: T, amount: Uint128) -> StdResult<CosmosMsg> {
+        self.call(Cw404ExecuteMsg::<Empty>::Transfer {
+            recipient: recipient.into(),
+            amount,
+        })
+    }
+
+    /// Build a fungible send that triggers `Receive` on `contract`
+    pub fn send<T: Into<String>>(
+        &self,
+        contract: T,
+        amount: Uint128,
+        msg: Binary,
+    ) -> StdResult<CosmosMsg> {
+        self.call(Cw404ExecuteMsg::<Empty>::Send {
+            contract: contract.into(),
+            amount,
+            msg,
+        })
+    }
+
+    /// Build a transfer of a single NFT
+    pub fn transfer_nft<T, U>(&self, recipient: T, token_id: U) -> StdResult<CosmosMsg>
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        self.call(Cw404ExecuteMsg::<Empty>::TransferNft {
+            recipient: recipient.into(),
+            token_id: token_id.into(),
+        })
+    }
+
+    /// Build a lock of an NFT the caller owns, keeping fungible transfers from
+    /// burning it. Moving the NFT unlocks it.
+    pub fn lock<T: Into<String>>(&self, token_id: T) -> StdResult<CosmosMsg> {
+        self.call(Cw404ExecuteMsg::<Empty>::Lock {
+            token_id: token_id.into(),
+        })
+    }
+
+    /// Build a send of a single NFT that triggers `ReceiveNft` on `contract`
+    pub fn send_nft<T, U>(&self, contract: T, token_id: U, msg: Binary) -> StdResult<CosmosMsg>
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        self.call(Cw404ExecuteMsg::<Empty>::SendNft {
+            contract: contract.into(),
+            token_id: token_id.into(),
+            msg,
+        })
+    }
+}
@@ -0,0 +1,22 @@
+mod helpers;
+mod metadata;
+mod msg;
+mod query;
+
+pub use cw20::Cw20ReceiveMsg;
+pub use cw721::Cw721ReceiveMsg;
+pub use cw_utils::Expiration;
+
+pub use crate::helpers::Cw404Contract;
+pub use crate::metadata::{Extension, Metadata, Trait};
+pub use crate::msg::{
+    Cw404ExecuteMsg, Cw404HookMsg, Cw404InstantiateMsg, Cw404MigrateMsg, FeeConfig, HookAction,
+    HookFailurePolicy, LaunchConfig, PauseTarget, Payout, Role,
+};
+pub use crate::query::{
+    AddressesResponse, BurnOrderResponse, ContractInfoResponse, Cw404QueryMsg, FeeConfigResponse,
+    HolderInfo, HoldersResponse, HookInfo, HooksResponse, IsLockedResponse, LaunchStatusResponse,
+    MetadataManagerResponse, MinterResponse, NftCountResponse, OwnedToken, PauseStatusResponse,
+    PausedTarget, PayoutsResponse, RoleMembersResponse, RolesResponse, SimulateTransferResponse,
+    StatsResponse, TierCount, TotalSupplyResponse, UserInfoResponse,
+};
@@ -0,0 +1,462 @@
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Binary, Coin, CosmosMsg, StdResult, Timestamp, Uint128, WasmMsg,
+};
+use cw20::{Cw20Coin, Cw20ReceiveMsg, Logo};
+use cw721::Cw721ReceiveMsg;
+use cw_utils::Expiration;
+use serde::de::{self, Deserializer, Visitor};
+
+#[cw_serde]
+pub struct Cw404InstantiateMsg {
+    // Name of the NFT contract
+    pub name: String,
+    // Symbol of the NFT contract
+    pub symbol: String,
+    // Decimals of erc404 token
+    pub decimals: u8,
+    // Supply of NFTs max
+    pub total_native_supply: Uint128,
+
+    pub minter: Option<String>,
+    /// Balances in base units handed out at instantiation. Holders receive the
+    /// NFTs their whole units back
+    #[serde(default)]
+    pub initial_balances: Vec<Cw20Coin>,
+    /// Receives whatever `initial_balances` leaves of the supply, without NFTs,
+    /// and is where `NativeMint` sells from. Defaults to the minter
+    pub sale_inventory: Option<String>,
+}
+
+/// Accepted by `migrate`. The stored cw2 version decides which state upgrades run.
+#[cw_serde]
+pub struct Cw404MigrateMsg {}
+
+/// Share of every withdrawal sent to `address`, in basis points.
+#[cw_serde]
+pub struct Payout {
+    pub address: String,
+    pub bps: u16,
+}
+
+/// Launch protection applied to every fungible transfer.
+///
+/// Before `trading_start` only allowlisted senders and the sale inventory can
+/// transfer. For `limits_duration` seconds afterwards, transfers above
+/// `max_tx_amount` and transfers leaving the recipient above `max_wallet_amount`
+/// fail. The sale inventory and registered pools are exempt from the limits.
+#[cw_serde]
+pub struct LaunchConfig {
+    pub trading_start: Timestamp,
+    pub limits_duration: u64,
+    pub max_tx_amount: Option<Uint128>,
+    pub max_wallet_amount: Option<Uint128>,
+}
+
+/// Fee taken from fungible transfers and paid to `recipient`, in basis points.
+///
+/// `buy_bps` applies when the sender is a registered pool, `sell_bps` when the
+/// recipient is one, and `transfer_bps` otherwise. Transfers involving a
+/// fee-exempt address or the fee recipient are free, and so are NFT transfers,
+/// which move exactly the unit backing the NFT.
+#[cw_serde]
+pub struct FeeConfig {
+    pub recipient: String,
+    pub transfer_bps: u16,
+    pub buy_bps: u16,
+    pub sell_bps: u16,
+}
+
+/// Permissions that the admin hands out to separate operator accounts.
+#[cw_serde]
+#[derive(Copy, Eq, PartialOrd, Ord)]
+pub enum Role {
+    /// Grants and revokes roles
+    Admin,
+    /// Mints tokens
+    Minter,
+    /// Edits token URIs and NFT metadata
+    MetadataManager,
+    /// Sets whitelist entries
+    WhitelistManager,
+    /// Manages the withdraw address and contract funds
+    Treasurer,
+    /// Pauses and unpauses transfers
+    Pauser,
+    /// Edits the cw20 marketing info and logo
+    Marketing,
+}
+
+impl Role {
+    pub const ALL: [Role; 7] = [
+        Role::Admin,
+        Role::Minter,
+        Role::MetadataManager,
+        Role::WhitelistManager,
+        Role::Treasurer,
+        Role::Pauser,
+        Role::Marketing,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Minter => "minter",
+            Role::MetadataManager => "metadata_manager",
+            Role::WhitelistManager => "whitelist_manager",
+            Role::Treasurer => "treasurer",
+            Role::Pauser => "pauser",
+            Role::Marketing => "marketing",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Operations that a pauser can freeze independently.
+#[cw_serde]
+#[derive(Copy, Eq, PartialOrd, Ord)]
+pub enum PauseTarget {
+    /// `Transfer`, `Send` and fungible `TransferFrom`
+    FungibleTransfers,
+    /// `TransferNft`, `SendNft` and `TransferFrom` by token id
+    NftTransfers,
+    /// `NativeMint` sales
+    NativeMint,
+    /// `Receive` and `ReceiveNft` handlers
+    Receive,
+}
+
+impl PauseTarget {
+    pub const ALL: [PauseTarget; 4] = [
+        PauseTarget::FungibleTransfers,
+        PauseTarget::NftTransfers,
+        PauseTarget::NativeMint,
+        PauseTarget::Receive,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PauseTarget::FungibleTransfers => "fungible_transfers",
+            PauseTarget::NftTransfers => "nft_transfers",
+            PauseTarget::NativeMint => "native_mint",
+            PauseTarget::Receive => "receive",
+        }
+    }
+}
+
+impl fmt::Display for PauseTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a failing hook contract does to the operation that triggered it.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum HookFailurePolicy {
+    /// Let the operation go through and report the failure as a `hook_failed`
+    /// attribute
+    Ignore,
+    /// Revert the whole operation
+    Revert,
+}
+
+/// Change reported by a [`Cw404HookMsg`].
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum HookAction {
+    /// `amount` base units moved from `from` to `to`
+    Transfer,
+    /// The NFT in `token_ids` moved from `from` to `to` with one whole unit
+    NftTransfer,
+    /// NFTs minted to `to` because its whole units went up
+    Mint,
+    /// NFTs burned from `from` because its whole units went down
+    Burn,
+}
+
+/// Sent to every hook contract, as `{"cw404_hook": {..}}`, after balances or
+/// NFTs change.
+///
+/// A fungible transfer is reported as a `Transfer` of what reaches `to`, plus
+/// one to the fee recipient when a fee is taken. `Mint` and `Burn` only list
+/// the NFTs it created or destroyed, with `amount` the whole units backing
+/// them, and do not move balance by themselves.
+#[cw_serde]
+pub struct Cw404HookMsg {
+    pub action: HookAction,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub amount: Uint128,
+    pub token_ids: Vec<String>,
+}
+
+impl Cw404HookMsg {
+    /// Serializes the message wrapped for the hook's execute entry point
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        to_json_binary(&HookExecuteMsg::Cw404Hook(self))
+    }
+
+    /// Creates a cosmos message calling `contract_addr` with this hook
+    pub fn into_cosmos_msg<T: Into<String>, C>(self, contract_addr: T) -> StdResult<CosmosMsg<C>> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: self.into_json_binary()?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+/// Execute message hook contracts must accept.
+#[cw_serde]
+enum HookExecuteMsg {
+    Cw404Hook(Cw404HookMsg),
+}
+
+// This is like Cw721ExecuteMsg but we add a Mint command for an owner
+// to make this stand-alone. You will likely want to remove mint and
+// use other control logic in any contract that inherits this.
+#[cw_serde]
+pub enum Cw404ExecuteMsg<T> {
+    // Transfer is a base message to move a token to another account without triggering actions
+    Receive(Cw20ReceiveMsg),
+    ReceiveNft(Cw721ReceiveMsg),
+    NativeMint {
+        recipient: String,
+        merkle_proof: Vec<Vec<u8>>,
+        hashed_address: Vec<u8>,
+    },
+    SetWithdrawAddress {
+        address: String,
+    },
+    RemoveWithdrawAddress {},
+    WithdrawFunds {
+        amount: Coin,
+    },
+    /// Pay out the contract's whole bank balance, every denom. Treasurer only
+    WithdrawAll {},
+    /// Split withdrawals between several addresses. An empty list falls back to
+    /// the withdraw address. Treasurer only
+    SetPayouts {
+        payouts: Vec<Payout>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Move the listed NFTs intact, together with the rest of `amount` as
+    /// fungible balance. `amount` must cover one whole unit per NFT
+    TransferWithIds {
+        recipient: String,
+        amount: Uint128,
+        token_ids: Vec<String>,
+    },
+    /// Ids of the sender's NFTs to burn first when whole units leave through a
+    /// plain transfer. Unlisted NFTs are burned afterwards, newest first
+    SetBurnOrder {
+        token_ids: Vec<String>,
+    },
+    /// Keep one of the sender's NFTs from being burned by fungible transfers
+    Lock {
+        #[serde(deserialize_with = "deserialize_token_id")]
+        token_id: String,
+    },
+    Unlock {
+        #[serde(deserialize_with = "deserialize_token_id")]
+        token_id: String,
+    },
+    TransferNft {
+        recipient: String,
+        #[serde(deserialize_with = "deserialize_token_id")]
+        token_id: String,
+    },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    SendNft {
+        contract: String,
+        #[serde(deserialize_with = "deserialize_token_id")]
+        token_id: String,
+        msg: Binary,
+    },
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    Approve {
+        spender: String,
+        #[serde(deserialize_with = "deserialize_token_id")]
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    ApproveAll {
+        operator: String,
+    },
+    RevokeAll {
+        operator: String,
+    },
+    /// Whitelisting burns every NFT of `target`; removing it from the whitelist
+    /// mints one for each whole token it holds. Whitelist manager only
+    SetWhitelist {
+        target: String,
+        state: bool,
+    },
+    SetBaseTokenUri {
+        id: u8,
+        uri: String,
+    },
+
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: T,
+    },
+    Burn {
+        token_id: String,
+    },
+
+    /// Stop the given operations, all of them when `targets` is empty.
+    /// With `until_height` set, the pause lifts itself at that block height.
+    /// Pauser only
+    Pause {
+        targets: Vec<PauseTarget>,
+        until_height: Option<u64>,
+    },
+    /// Resume the given operations, all of them when `targets` is empty.
+    /// Pauser only
+    Unpause {
+        targets: Vec<PauseTarget>,
+    },
+    /// Configure the trading start and launch limits. Admin only
+    SetLaunchConfig {
+        config: LaunchConfig,
+    },
+    /// Allow or disallow `address` to transfer before trading starts. Admin only
+    SetLaunchAllowlist {
+        address: String,
+        allowed: bool,
+    },
+    /// Register or unregister a liquidity pool. Admin only
+    SetPool {
+        address: String,
+        is_pool: bool,
+    },
+    /// Set or, with `None`, remove the fungible transfer fee. Admin only
+    SetFeeConfig {
+        config: Option<FeeConfig>,
+    },
+    /// Exempt `address` from transfer fees, or make it pay them again. Admin only
+    SetFeeExempt {
+        address: String,
+        exempt: bool,
+    },
+    /// Add `address` to, or remove it from, the blocklist. Blocked addresses
+    /// cannot send, receive, approve or mint. Admin only
+    SetBlocklist {
+        address: String,
+        blocked: bool,
+    },
+    /// Give `address` a role. Admin only
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    /// Take a role away from `address`. Admin only
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    /// Send a `Cw404HookMsg` to `address` on every transfer, mint and burn,
+    /// or change its failure policy if it is already registered. Admin only
+    AddHook {
+        address: String,
+        policy: HookFailurePolicy,
+    },
+    /// Stop notifying `address`. Admin only
+    RemoveHook {
+        address: String,
+    },
+
+    /// Set the extension copied onto NFTs minted in `tier` from now on
+    SetTierMetadata {
+        tier: u8,
+        extension: T,
+    },
+    /// Replace the extension of an existing NFT
+    UpdateNftMetadata {
+        token_id: String,
+        extension: T,
+    },
+    /// Permanently prevent any further metadata changes
+    FreezeMetadata {},
+    /// Change the collection details. Unset fields are kept, and an empty
+    /// `description`, `image` or `external_link` clears it. Admin only
+    UpdateContractInfo {
+        name: Option<String>,
+        symbol: Option<String>,
+        description: Option<String>,
+        image: Option<String>,
+        external_link: Option<String>,
+    },
+    /// Permanently prevent any further `UpdateContractInfo`. Admin only
+    FreezeContractInfo {},
+    /// cw20 marketing info. Unset fields are kept and an empty string clears
+    /// one. `marketing` hands the sender's marketing role to that address, or
+    /// gives it up when empty. Marketing only
+    UpdateMarketing {
+        project: Option<String>,
+        description: Option<String>,
+        marketing: Option<String>,
+    },
+    /// Replace the cw20 logo. Embedded logos are limited to 5KB. Marketing only
+    UploadLogo(Logo),
+}
+
+/// cw721 sends token ids as strings, while earlier integrators of this contract
+/// sent them as JSON numbers. Both are accepted and normalised to a string here;
+/// the execute handler parses and validates the id.
+fn deserialize_token_id<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct TokenIdVisitor;
+
+    impl<'de> Visitor<'de> for TokenIdVisitor {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a token id as a string or an unsigned integer")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+            Ok(v.to_string())
+        }
+    }
+
+    deserializer.deserialize_any(TokenIdVisitor)
+}
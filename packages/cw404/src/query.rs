@@ -0,0 +1,405 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+
+use crate::msg::{FeeConfig, HookFailurePolicy, LaunchConfig, PauseTarget, Payout, Role};
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum Cw404QueryMsg {
+    #[returns(cw721::OwnerOfResponse)]
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    /// Return the approval of a single spender for the given token
+    #[returns(cw721::ApprovalResponse)]
+    Approval {
+        token_id: String,
+        spender: String,
+        include_expired: Option<bool>,
+    },
+
+    /// Return the approvals that a token has
+    #[returns(cw721::ApprovalsResponse)]
+    Approvals {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    /// Return the approval of an operator for all of the owner's tokens
+    #[returns(cw721::OperatorResponse)]
+    Operator {
+        owner: String,
+        operator: String,
+        include_expired: Option<bool>,
+    },
+
+    /// List all operators that can access all of the owner's tokens
+    #[returns(cw721::OperatorsResponse)]
+    AllOperators {
+        owner: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Everything about one account. Owned NFTs, and which of them are
+    /// locked, are paged by ascending id
+    #[returns(UserInfoResponse)]
+    UserInfo {
+        address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Preview a `Transfer` of `amount` from `from` to `to` without executing it
+    #[returns(SimulateTransferResponse)]
+    SimulateTransfer {
+        from: String,
+        to: String,
+        amount: Uint128,
+    },
+
+    /// Whether the NFT is protected from burns by its owner
+    #[returns(IsLockedResponse)]
+    IsLocked { token_id: String },
+
+    /// NFTs of `owner` that plain transfers burn first, in order
+    #[returns(BurnOrderResponse)]
+    BurnOrder { owner: String },
+
+    /// Live NFTs, i.e. minted and not burned
+    #[returns(cw721::NumTokensResponse)]
+    NumTokens {},
+
+    /// Supply, NFT and holder figures of the whole collection
+    #[returns(StatsResponse)]
+    Stats {},
+
+    /// Collection details, with the supply in whole tokens
+    #[returns(ContractInfoResponse)]
+    ContractInfo {},
+
+    #[returns(cw721::NftInfoResponse<crate::Extension>)]
+    NftInfo { token_id: String },
+
+    #[returns(cw20::BalanceResponse)]
+    Balance { address: String },
+
+    /// cw20 token info, with the supply in base units
+    #[returns(cw20::TokenInfoResponse)]
+    TokenInfo {},
+
+    /// Addresses holding a balance, as in cw20
+    #[returns(cw20::AllAccountsResponse)]
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Balance of `address` at the start of block `height`, before any of its
+    /// transactions
+    #[returns(cw20::BalanceResponse)]
+    BalanceAtHeight { address: String, height: u64 },
+
+    /// Supply in base units at the start of block `height`
+    #[returns(TotalSupplyResponse)]
+    TotalSupplyAtHeight { height: u64 },
+
+    /// NFTs owned by `address` at the start of block `height`
+    #[returns(NftCountResponse)]
+    NftCountAtHeight { address: String, height: u64 },
+
+    /// Balance, NFT count and exemption of every holder
+    #[returns(HoldersResponse)]
+    Holders {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(cw20::MarketingInfoResponse)]
+    MarketingInfo {},
+
+    /// The embedded logo, if one was uploaded
+    #[returns(cw20::DownloadLogoResponse)]
+    DownloadLogo {},
+
+    #[returns(cw721::TokensResponse)]
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(cw721::TokensResponse)]
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(cw721::AllNftInfoResponse<crate::Extension>)]
+    AllNftInfo {
+        token_id: String,
+        // unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+
+    // Return the minter
+    #[returns(MinterResponse)]
+    Minter {},
+
+    #[returns(MetadataManagerResponse)]
+    MetadataManager {},
+
+    /// List the addresses holding `role`
+    #[returns(RoleMembersResponse)]
+    RoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// List the roles held by `address`
+    #[returns(RolesResponse)]
+    Roles { address: String },
+
+    /// Where withdrawn funds are sent
+    #[returns(PayoutsResponse)]
+    Payouts {},
+
+    /// Operations currently paused
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
+
+    /// Trading start and launch limits, with whether they currently apply
+    #[returns(LaunchStatusResponse)]
+    LaunchStatus {},
+
+    /// Addresses allowed to transfer before trading starts
+    #[returns(AddressesResponse)]
+    LaunchAllowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Current transfer fee settings
+    #[returns(FeeConfigResponse)]
+    FeeConfig {},
+
+    /// Addresses exempt from transfer fees
+    #[returns(AddressesResponse)]
+    FeeExempt {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Addresses on the blocklist
+    #[returns(AddressesResponse)]
+    Blocklist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Registered liquidity pools
+    #[returns(AddressesResponse)]
+    Pools {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Contracts notified of transfers, mints and burns
+    #[returns(HooksResponse)]
+    Hooks {},
+}
+
+// Shows who can mint these tokens
+#[cw_serde]
+pub struct MinterResponse {
+    pub minter: Option<String>,
+}
+
+#[cw_serde]
+pub struct MetadataManagerResponse {
+    pub managers: Vec<String>,
+    pub frozen: bool,
+}
+
+#[cw_serde]
+pub struct RoleMembersResponse {
+    pub role: Role,
+    pub members: Vec<String>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct PayoutsResponse {
+    pub withdraw_address: Option<String>,
+    /// Takes precedence over `withdraw_address` when not empty
+    pub payouts: Vec<Payout>,
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub paused: Vec<PausedTarget>,
+}
+
+#[cw_serde]
+pub struct PausedTarget {
+    pub target: PauseTarget,
+    /// Height at which the pause lifts itself, if any
+    pub until_height: Option<u64>,
+}
+
+#[cw_serde]
+pub struct LaunchStatusResponse {
+    pub config: Option<LaunchConfig>,
+    pub trading_enabled: bool,
+    pub limits_active: bool,
+}
+
+#[cw_serde]
+pub struct FeeConfigResponse {
+    pub config: Option<FeeConfig>,
+}
+
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<HookInfo>,
+}
+
+#[cw_serde]
+pub struct HookInfo {
+    pub address: String,
+    pub policy: HookFailurePolicy,
+}
+
+#[cw_serde]
+pub struct AddressesResponse {
+    pub addresses: Vec<String>,
+}
+
+#[cw_serde]
+pub struct SimulateTransferResponse {
+    /// Why the transfer would fail. The other fields are empty when set
+    pub error: Option<String>,
+    /// NFTs burned from `from`, in order
+    pub burned_ids: Vec<String>,
+    /// Number of NFTs minted to `to`
+    pub minted: u32,
+    pub fee: Uint128,
+    /// What reaches `to` after the fee
+    pub received: Uint128,
+}
+
+#[cw_serde]
+pub struct TotalSupplyResponse {
+    pub total_supply: Uint128,
+}
+
+#[cw_serde]
+pub struct NftCountResponse {
+    pub count: u32,
+}
+
+#[cw_serde]
+pub struct IsLockedResponse {
+    pub locked: bool,
+}
+
+#[cw_serde]
+pub struct BurnOrderResponse {
+    pub token_ids: Vec<String>,
+}
+
+#[cw_serde]
+pub struct HoldersResponse {
+    pub holders: Vec<HolderInfo>,
+    /// Addresses with a non-zero balance
+    pub holder_count: u64,
+    /// Addresses owning at least one NFT
+    pub nft_holder_count: u64,
+}
+
+#[cw_serde]
+pub struct HolderInfo {
+    pub address: String,
+    pub balance: Uint128,
+    pub nft_count: u32,
+    /// Whitelisted, so its balance is not backed by NFTs
+    pub exempt: bool,
+}
+
+#[cw_serde]
+pub struct StatsResponse {
+    /// In base units
+    pub total_supply: Uint128,
+    /// In whole tokens
+    pub total_native_supply: Uint128,
+    /// Base units outside the sale inventory
+    pub circulating_supply: Uint128,
+    /// Base units held by whitelisted addresses, which no NFTs back
+    pub exempt_supply: Uint128,
+    pub live_nfts: Uint128,
+    /// NFTs ever minted, the highest token id so far
+    pub minted: Uint128,
+    pub burned: Uint128,
+    /// Live NFTs per tier, for every tier
+    pub tiers: Vec<TierCount>,
+    pub holder_count: u64,
+    pub nft_holder_count: u64,
+}
+
+#[cw_serde]
+pub struct TierCount {
+    pub tier: u8,
+    pub count: u64,
+}
+
+#[cw_serde]
+pub struct UserInfoResponse {
+    /// In base units
+    pub balance: Uint128,
+    /// Whole tokens in `balance`, one per NFT unless `exempt`
+    pub whole_units: Uint128,
+    /// Base units of `balance` beyond its whole tokens
+    pub fractional: Uint128,
+    pub nft_count: u32,
+    /// Page of owned NFTs
+    pub owned: Vec<OwnedToken>,
+    /// NFTs of `owned` that fungible transfers never burn
+    pub locked: Vec<String>,
+    /// Whitelisted, so its balance is not backed by NFTs
+    pub exempt: bool,
+    pub blocked: bool,
+    /// Fungible allowances granted to spenders
+    pub allowances: Vec<cw20::AllowanceInfo>,
+    /// Operators approved for all NFTs
+    pub operators: Vec<String>,
+}
+
+#[cw_serde]
+pub struct OwnedToken {
+    pub token_id: String,
+    pub tier: u8,
+}
+
+#[cw_serde]
+pub struct ContractInfoResponse {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    /// In whole tokens, i.e. NFTs. `TokenInfo` reports the same supply in base
+    /// units, `total_supply * 10^decimals`
+    pub total_supply: Uint128,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub external_link: Option<String>,
+    /// Once set, `UpdateContractInfo` is rejected
+    #[serde(default)]
+    pub frozen: bool,
+}